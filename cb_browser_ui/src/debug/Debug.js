@@ -1,21 +1,59 @@
 import React from 'react';
-import { Button, InputNumber } from 'antd';
+import { Button, InputNumber, Input, Select } from 'antd';
+const Option = Select.Option;
 import { fmtId } from '../browser_utils/Utils';
 
 export const initialState = {
     show: false,
-    planGridSettings: {
-        n: 10,
-        spacing: 200
-    },
-    spawnCarsSettings: {
-        triesPerLane: 50
+    stepSettings: {
+        nTicks: 1
     },
     logLastEntry: 0,
     logTextStart: 0,
     logFirstEntry: 0,
     logEntries: [],
-    logText: []
+    logText: [],
+    logQuery: {
+        minLevel: "Debug",
+        topic: "",
+        fromType: "",
+        from: null,
+        since: null,
+        until: null,
+        page: 0
+    },
+    logQueryResult: null,
+    logQueryError: null
+}
+
+const LOG_QUERY_PAGE_SIZE = 200;
+
+function typeIdOf(shortTypeName) {
+    return Object.keys(window.cbTypeIdMapping).find(typeId => {
+        const typeSplit = window.cbTypeIdMapping[typeId].split("::");
+        return typeSplit[typeSplit.length - 1] === shortTypeName;
+    });
+}
+
+// returns a state update that shows why the query couldn't be run, if it couldn't
+function queryLog(logQuery) {
+    const fromType = logQuery.fromType && typeIdOf(logQuery.fromType);
+    if (logQuery.fromType && !fromType) {
+        return oldState => update(oldState, {
+            debug: { logQueryError: { $set: "Unknown actor type '" + logQuery.fromType + "'" } }
+        });
+    }
+    cbRustBrowser.query_log({
+        min_level: logQuery.minLevel,
+        topic: logQuery.topic || null,
+        from: logQuery.from,
+        from_type: fromType ? parseInt(fromType) : null,
+        since: logQuery.since,
+        until: logQuery.until,
+        skip: logQuery.page * LOG_QUERY_PAGE_SIZE,
+        limit: LOG_QUERY_PAGE_SIZE
+    });
+    return oldState => update(oldState, { debug: { logQueryError: { $set: null } } });
 }
 
 export const settingsSpec = {
@@ -27,6 +65,12 @@ let refreshInterval = null;
 export function Windows(props) {
     const { state, setState } = props;
 
+    const filterByActor = from => () => {
+        const logQuery = update(state.debug.logQuery, { from: { $set: from }, page: { $set: 0 } });
+        setState(oldState => update(oldState, { debug: { logQuery: { $set: logQuery } } }));
+        setState(queryLog(logQuery));
+    };
+
     if (state.debug.show) {
         if (!refreshInterval) {
             refreshInterval = setInterval(() => cbRustBrowser.get_newest_log_messages(), 300);
@@ -53,45 +97,17 @@ export function Windows(props) {
             <h1>Debugging</h1>
             <details>
                 <summary>Debug Actions</summary>
-                {state.planning.currentProject
-                    ? <div key="gridPlanning">
-                        Grid size
-                    <InputNumber
-                            value={state.debug.planGridSettings.n}
-                            onChange={(n) => setState(oldState => update(oldState, {
-                                debug: { planGridSettings: { n: { $set: n } } }
-                            }))}
-                            min={0} />
-                        Spacing
-                    <InputNumber
-                            value={state.debug.planGridSettings.spacing}
-                            onChange={(spacing) => setState(oldState => update(oldState, {
-                                debug: { planGridSettings: { spacing: { $set: spacing } } }
-                            }))}
-                            step={10.0}
-                            min={0} />
-
-                        <Button
-                            onClick={() => cbRustBrowser.plan_grid(
-                                state.planning.currentProject,
-                                state.debug.planGridSettings.n,
-                                state.debug.planGridSettings.spacing
-                            )
-                            }>Plan grid</Button>
-                    </div>
-                    : <div>(open a project to plan a grid)</div>
-                }
-                <div key="carSpawning">
-                    Cars per lane (tries)
+                <div key="stepping">
+                    Ticks
                 <InputNumber
-                        value={state.debug.spawnCarsSettings.triesPerLane}
-                        onChange={(triesPerLane) => setState(oldState => update(oldState, {
-                            debug: { spawnCarsSettings: { triesPerLane: { $set: triesPerLane } } }
+                        value={state.debug.stepSettings.nTicks}
+                        onChange={(nTicks) => setState(oldState => update(oldState, {
+                            debug: { stepSettings: { nTicks: { $set: nTicks } } }
                         }))}
                         min={1} /> <Button
-                            onClick={() => cbRustBrowser.spawn_cars(
-                                state.debug.spawnCarsSettings.triesPerLane
-                            )}>Spawn cars</Button>
+                            onClick={() => cbRustBrowser.step_sim(
+                                state.debug.stepSettings.nTicks
+                            )}>Step</Button>
                 </div>
                 <div key="rendering">
                     <Button
@@ -114,18 +130,71 @@ export function Windows(props) {
             </details>
             <details>
                 <summary>Simulation Log</summary>
-                <div className="scrollableLog">{state.debug.logEntries.map((entry, i) => {
-                    let ts = state.debug.logTextStart;
-                    let topic = state.debug.logText.slice(entry.topic_start - ts, entry.message_start - ts);
-                    let message = state.debug.logText.slice(entry.message_start - ts, entry.message_start + entry.message_len - ts);
-                    return <div key={i} className={entry.level}>{state.debug.logFirstEntry + i} [{topic}] {fmtId(entry.from)}: {message}</div>
-                }
-                )}</div>
+                <LogQueryForm state={state} setState={setState} />
+                {state.debug.logQueryResult
+                    ? <div className="scrollableLog">{state.debug.logQueryResult.entries.map(([idx, entry]) => {
+                        let text = state.debug.logQueryResult.text;
+                        let topic = text.slice(entry.topic_start, entry.message_start);
+                        let message = text.slice(entry.message_start, entry.message_start + entry.message_len);
+                        return <div key={idx} className={entry.level}>{idx} @{entry.instant} [{topic}] <a onClick={filterByActor(entry.from)}>{fmtId(entry.from)}</a>: {message}</div>
+                    })}</div>
+                    : <div className="scrollableLog">{state.debug.logEntries.map((entry, i) => {
+                        let ts = state.debug.logTextStart;
+                        let topic = state.debug.logText.slice(entry.topic_start - ts, entry.message_start - ts);
+                        let message = state.debug.logText.slice(entry.message_start - ts, entry.message_start + entry.message_len - ts);
+                        return <div key={i} className={entry.level}>{state.debug.logFirstEntry + i} [{topic}] <a onClick={filterByActor(entry.from)}>{fmtId(entry.from)}</a>: {message}</div>
+                    }
+                    )}</div>}
             </details>
         </div>,
         connectionIssue && <div className="window connection">{connectionIssue}</div>];
 }
 
+function LogQueryForm(props) {
+    const { state, setState } = props;
+    const logQuery = state.debug.logQuery;
+    const setLogQuery = change => setState(oldState => update(oldState, {
+        debug: { logQuery: change }
+    }));
+    const runQuery = page => {
+        setLogQuery({ page: { $set: page } });
+        setState(queryLog(update(logQuery, { page: { $set: page } })));
+    };
+    const result = state.debug.logQueryResult;
+
+    return <div key="logQuery">
+        <Select value={logQuery.minLevel}
+            onChange={minLevel => setLogQuery({ minLevel: { $set: minLevel } })}>
+            {["Debug", "Info", "Warning", "Error"].map(level =>
+                <Option key={level} value={level}>{level}</Option>
+            )}
+        </Select>
+        <Input placeholder="Topic" value={logQuery.topic}
+            onChange={e => setLogQuery({ topic: { $set: e.target.value } })} />
+        <Input placeholder="Actor type" value={logQuery.fromType}
+            onChange={e => setLogQuery({ fromType: { $set: e.target.value } })} />
+        Ticks
+        <InputNumber placeholder="since" value={logQuery.since} min={0}
+            onChange={since => setLogQuery({ since: { $set: since === "" ? null : since } })} />
+        <InputNumber placeholder="until" value={logQuery.until} min={0}
+            onChange={until => setLogQuery({ until: { $set: until === "" ? null : until } })} />
+        {logQuery.from && <Button
+            onClick={() => setLogQuery({ from: { $set: null } })}>Any actor, not {fmtId(logQuery.from)}</Button>}
+        <Button onClick={() => runQuery(0)}>Search</Button>
+        {state.debug.logQueryError && <span className="Error"> {state.debug.logQueryError} </span>}
+        {result && [
+            <Button key="older" disabled={(logQuery.page + 1) * LOG_QUERY_PAGE_SIZE >= result.nMatching}
+                onClick={() => runQuery(logQuery.page + 1)}>Older</Button>,
+            <Button key="newer" disabled={logQuery.page === 0}
+                onClick={() => runQuery(logQuery.page - 1)}>Newer</Button>,
+            <span key="matching"> {result.nMatching} matching </span>,
+            <Button key="live" onClick={() => setState(oldState => update(oldState, {
+                debug: { logQueryResult: { $set: null } }
+            }))}>Back to live log</Button>
+        ]}
+    </div>
+}
+
 export function bindInputs(state, setState) {
     const inputActions = {
         "toggleDebugView": () => setState(oldState => update(oldState, {
@@ -4,58 +4,9 @@ use stdweb::serde::Serde;
 use stdweb::js_export;
 use SYSTEM;
 
-#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
-pub fn plan_grid(project_id: Serde<::planning::ProjectID>, n: Serde<isize>, spacing: Serde<f32>) {
-    let system = unsafe { &mut *SYSTEM };
-    let world = &mut system.world();
-
-    let plan_manager = ::planning::PlanManagerID::global_first(world);
-
-    use ::transport::transport_planning::RoadIntent;
-    use ::planning::{GestureID, GestureIntent};
-    use ::descartes::P2;
-
-    for x in -n.0 / 2..n.0 / 2 {
-        let id = GestureID::new();
-        let p1 = P2::new(x as f32 * spacing.0, (-n.0 / 2) as f32 * spacing.0);
-        let p2 = P2::new(x as f32 * spacing.0, (n.0 / 2) as f32 * spacing.0);
-        plan_manager.start_new_gesture(
-            project_id.0,
-            id,
-            GestureIntent::Road(RoadIntent::new(3, 3)),
-            p1,
-            world,
-        );
-        plan_manager.add_control_point(project_id.0, id, p2, true, true, world);
-    }
-
-    for y in -n.0 / 2..n.0 / 2 {
-        let id = GestureID::new();
-        let p1 = P2::new((-n.0 / 2) as f32 * spacing.0, y as f32 * spacing.0);
-        let p2 = P2::new((n.0 / 2) as f32 * spacing.0, y as f32 * spacing.0);
-        plan_manager.start_new_gesture(
-            project_id.0,
-            id,
-            GestureIntent::Road(RoadIntent::new(3, 3)),
-            p1,
-            world,
-        );
-        plan_manager.add_control_point(project_id.0, id, p2, true, true, world);
-    }
-}
-
-#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
-pub fn spawn_cars(tries_per_lane: usize) {
-    let system = unsafe { &mut *SYSTEM };
-    let world = &mut system.world();
-    for _ in 0..tries_per_lane {
-        ::transport::lane::LaneID::global_broadcast(world).manually_spawn_car_add_lane(world);
-    }
-}
-
 use kay::{World, ActorSystem};
 use compact::{CVec, CString};
-use log::{LogID, LogRecipient, LogRecipientID, Entry};
+use log::{LogID, LogRecipient, LogRecipientID, Entry, LogQuery};
 
 #[derive(Compact, Clone)]
 pub struct LogUI {
@@ -74,7 +25,7 @@ impl LogRecipient for LogUI {
         entries: &CVec<Entry>,
         text: &CString,
         effective_last: u32,
-        effective_text_start: u32,
+        effective_text_start: u64,
         _: &mut World,
     ) {
         js! {
@@ -94,7 +45,8 @@ impl LogRecipient for LogUI {
                 window.cbReactApp.boundSetState(oldState => update(oldState, {
                     debug: {
                         logLastEntry: {"$set": @{effective_last + entries.len() as u32}},
-                        logTextStart: {"$set": @{effective_text_start}},
+                        // exact up to 2^53, which log text doesn't reach
+                        logTextStart: {"$set": @{effective_text_start as f64}},
                         logFirstEntry: {"$set": @{effective_last}},
                         logEntries: {"$set": entries},
                         logText: {"$set": text}
@@ -103,6 +55,26 @@ impl LogRecipient for LogUI {
             }
         };
     }
+
+    fn receive_log_query_result(
+        &mut self,
+        entries: &CVec<(u32, Entry)>,
+        text: &CString,
+        n_matching: u32,
+        _: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                debug: {
+                    logQueryResult: {"$set": {
+                        entries: @{Serde(entries)},
+                        text: @{Serde(text)},
+                        nMatching: @{n_matching}
+                    }}
+                }
+            }));
+        };
+    }
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
@@ -127,6 +99,14 @@ pub fn get_newest_log_messages() {
     );
 }
 
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn query_log(query: Serde<LogQuery>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+
+    LogID::global_first(world).query(query.0, LogUIID::local_first(world).into(), world);
+}
+
 mod kay_auto;
 pub use self::kay_auto::*;
 
@@ -32,6 +32,12 @@ pub mod browser_utils;
 
 // TODO: not thread safe for now
 static mut SYSTEM: *mut ActorSystem = 0 as *mut ActorSystem;
+static mut SESSION: Option<access::SessionID> = None;
+
+/// The session this client joined the server with, needed for planning and time messages
+pub fn session() -> access::SessionID {
+    unsafe { SESSION.expect("Should have joined a session") }
+}
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn start() {
@@ -45,6 +51,18 @@ pub fn start() {
     .into_string()
     .unwrap();
 
+    let session = js! {
+        return window.cbSession;
+    }
+    .into_string()
+    .unwrap();
+
+    unsafe {
+        SESSION = Some(access::SessionID(
+            util::random::Uuid::parse_str(&session).expect("Session should be a UUID"),
+        ))
+    };
+
     let mut network_settings = ::std::collections::HashMap::from(
         js! {
             return window.cbNetworkSettings;
@@ -30,13 +30,18 @@ export const initialState = {
             zoneOutlineGroups: new Map(LAND_USES.map(landUse => [landUse, new Map()])),
             buildingOutlinesGroup: new Map(),
         },
-        roadInfos: {}
+        roadInfos: {},
+        constructionCost: 0,
     },
     master: {
         gestures: {}
     },
     projects: {
     },
+    conflicts: {
+    },
+    conflictResolutions: {
+    },
     currentProject: null,
     hoveredControlPoint: {},
     hoveredInsertPoint: null,
@@ -247,6 +252,10 @@ export function ShapesAndLayers(props) {
 
         let { gestureId: hoveredGestureId, pointIdx: hoveredPointIdx } = state.planning.hoveredControlPoint;
 
+        const conflicts = state.planning.conflicts[state.planning.currentProject];
+        const outdatedGestures = new Set(conflicts ? conflicts.outdated_gestures : []);
+        const sharedGestures = new Set(conflicts ? conflicts.shared_gestures.map(([_, gestureId]) => gestureId) : []);
+
         for (let gestureId of Object.keys(gestures)) {
             const gesture = gestures[gestureId];
 
@@ -266,7 +275,11 @@ export function ShapesAndLayers(props) {
                         1.0, 0.0,
                         ...(isHovered
                             ? colors.controlPointHover
-                            : (gesture.fromMaster ? colors.controlPointMaster : colors.controlPointCurrentProject))
+                            : outdatedGestures.has(gestureId)
+                                ? colors.controlPointOutdated
+                                : sharedGestures.has(gestureId)
+                                    ? colors.controlPointShared
+                                    : (gesture.fromMaster ? colors.controlPointMaster : colors.controlPointCurrentProject))
                     ]);
 
                     controlPointsInteractables.push(<Interactive3DShape
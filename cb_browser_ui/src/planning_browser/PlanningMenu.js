@@ -10,6 +10,7 @@ import { Toolbar } from '../toolbar';
 
 function switchToProject(projectId) {
     console.log("switching to", projectId);
+    cbRustBrowser.get_project_conflicts(projectId);
 
     return oldState => update(oldState, {
         planning: { currentProject: { $set: projectId } }
@@ -38,6 +39,37 @@ function startNewProject(oldState) {
     });
 }
 
+function checkConflicts(oldState) {
+    if (oldState.planning.currentProject) cbRustBrowser.get_project_conflicts(oldState.planning.currentProject);
+    return oldState;
+}
+
+function resolveGesture(projectId, gestureId, resolution) {
+    return oldState => update(oldState, {
+        planning: {
+            conflictResolutions: {
+                [projectId]: { $apply: resolutions => Object.assign({}, resolutions, { [gestureId]: resolution }) }
+            }
+        }
+    });
+}
+
+function rebaseProject(oldState) {
+    const projectId = oldState.planning.currentProject;
+    const conflicts = projectId && oldState.planning.conflicts[projectId];
+    if (conflicts) {
+        const resolutions = oldState.planning.conflictResolutions[projectId] || {};
+        cbRustBrowser.rebase_project(projectId, conflicts.outdated_gestures.map(gestureId => [gestureId, resolutions[gestureId]]));
+        cbRustBrowser.get_project_conflicts(projectId);
+        return update(oldState, {
+            planning: {
+                conflictResolutions: { $unset: [projectId] }
+            }
+        });
+    }
+    return oldState;
+}
+
 function undo(oldState) {
     if (oldState.planning.currentProject) {
         cbRustBrowser.undo(oldState.planning.currentProject);
@@ -63,6 +95,42 @@ function redo(oldState) {
     return oldState
 }
 
+function Conflicts(props) {
+    const { state, setState } = props;
+    const projectId = state.planning.currentProject;
+    const conflicts = state.planning.conflicts[projectId];
+
+    if (!conflicts) {
+        return null;
+    } else if (!conflicts.outdated_gestures.length && !conflicts.shared_gestures.length && !conflicts.overlapping_cells.length) {
+        return <span>No conflicts</span>;
+    }
+
+    const resolutions = state.planning.conflictResolutions[projectId] || {};
+    const allResolved = conflicts.outdated_gestures.every(gestureId => resolutions[gestureId]);
+    const nOtherProjects = new Set(conflicts.shared_gestures.map(([otherId, _]) => otherId)
+        .concat(conflicts.overlapping_cells.map(([otherId, _]) => otherId))).size;
+
+    return [
+        nOtherProjects > 0 && <span>
+            Shares {conflicts.shared_gestures.length} gestures and overlaps in {conflicts.overlapping_cells.length} places with {nOtherProjects} other projects
+        </span>,
+        conflicts.outdated_gestures.map(gestureId =>
+            <Select
+                style={{ width: 220 }}
+                placeholder={"Gesture '" + gestureId.slice(0, 3).toUpperCase() + "' changed in master"}
+                value={resolutions[gestureId]}
+                onChange={(value) => setState(resolveGesture(projectId, gestureId, value))}
+            >
+                <Option value="KeepProject">Keep this project's version</Option>
+                <Option value="TakeMaster">Take the master plan's version</Option>
+            </Select>
+        ),
+        conflicts.outdated_gestures.length > 0 &&
+        <Button disabled={!allResolved} onClick={() => setState(rebaseProject)}>Rebase</Button>
+    ];
+}
+
 export function Tools(props) {
     const { state, setState } = props;
     return [
@@ -96,7 +164,9 @@ export function Tools(props) {
             state.planning.currentProject && [
                 <Button type="primary"
                     onClick={() => setState(implementProject)}
-                >Implement</Button>,
+                >Implement (costs {Math.round(state.planning.rendering.constructionCost)})</Button>,
+                <Button onClick={() => setState(checkConflicts)}>Check conflicts</Button>,
+                <Conflicts state={state} setState={setState} />,
                 <Toolbar id="planning-history-toolbar"
                     options={{
                         undo: { description: "Undo", disabled: !state.planning.projects[state.planning.currentProject] || !state.planning.projects[state.planning.currentProject].undoable_history.length },
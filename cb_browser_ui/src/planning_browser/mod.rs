@@ -8,6 +8,7 @@ use planning::{ProjectID, Project, GestureID, PrototypeID, PlanHistory, PlanResu
 PlanHistoryUpdate, ProjectUpdate, PlanResultUpdate, ActionGroups};
 use ::land_use::zone_planning::{LandUse, LAND_USES};
 use planning::ui::{PlanningUI, PlanningUIID};
+use planning::conflicts::{ProjectConflicts, GestureResolution};
 use browser_utils::{updated_groups_to_js, to_js_mesh, FrameListener, FrameListenerID};
 
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
@@ -30,6 +31,7 @@ pub fn move_gesture_point(
         point_idx,
         new_position.0,
         done_moving,
+        ::session(),
         world,
     );
 }
@@ -48,6 +50,7 @@ pub fn start_new_gesture(
         gesture_id.0,
         intent.0,
         start.0,
+        ::session(),
         world,
     )
 }
@@ -68,6 +71,7 @@ pub fn add_control_point(
         new_point.0,
         add_to_end,
         done_adding,
+        ::session(),
         world,
     )
 }
@@ -86,6 +90,7 @@ pub fn insert_control_point(
         gesture_id.0,
         new_point.0,
         done_inserting,
+        ::session(),
         world,
     )
 }
@@ -104,6 +109,7 @@ pub fn split_gesture(
         gesture_id.0,
         split_at.0,
         done_inserting,
+        ::session(),
         world,
     )
 }
@@ -126,6 +132,7 @@ pub fn set_n_lanes(
             n_lanes_backward: n_lanes_backward as u8,
         }),
         done_changing,
+        ::session(),
         world,
     )
 }
@@ -134,28 +141,103 @@ pub fn set_n_lanes(
 pub fn undo(project_id: Serde<::planning::ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::planning::PlanManagerID::global_first(world).undo(project_id.0, world)
+    ::planning::PlanManagerID::global_first(world).undo(project_id.0, ::session(), world)
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn redo(project_id: Serde<::planning::ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::planning::PlanManagerID::global_first(world).redo(project_id.0, world)
+    ::planning::PlanManagerID::global_first(world).redo(project_id.0, ::session(), world)
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn implement_project(project_id: Serde<::planning::ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::planning::PlanManagerID::global_first(world).implement(project_id.0, world);
+    ::planning::PlanManagerID::global_first(world).implement(project_id.0, ::session(), world);
 }
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn start_new_project(project_id: Serde<::planning::ProjectID>) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::planning::PlanManagerID::global_first(world).start_new_project(project_id.0, world);
+    ::planning::PlanManagerID::global_first(world).start_new_project(
+        project_id.0,
+        ::session(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_project_info(
+    project_id: Serde<::planning::ProjectID>,
+    title: String,
+    description: String,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).set_project_info(
+        project_id.0,
+        title.into(),
+        description.into(),
+        ::session(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn propose_project(project_id: Serde<::planning::ProjectID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).propose(project_id.0, ::session(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn withdraw_project(project_id: Serde<::planning::ProjectID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).withdraw(project_id.0, ::session(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn approve_project(project_id: Serde<::planning::ProjectID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).approve(project_id.0, ::session(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn reject_project(project_id: Serde<::planning::ProjectID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).reject(project_id.0, ::session(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_project_conflicts(project_id: Serde<::planning::ProjectID>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).get_conflicts(
+        BrowserPlanningUIID::local_first(world).into(),
+        project_id.0,
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn rebase_project(
+    project_id: Serde<::planning::ProjectID>,
+    resolutions: Serde<Vec<(::planning::GestureID, GestureResolution)>>,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::planning::PlanManagerID::global_first(world).rebase(
+        project_id.0,
+        resolutions.0.into(),
+        ::session(),
+        world,
+    );
 }
 
 #[derive(Compact, Clone)]
@@ -374,6 +456,7 @@ impl PlanningUI for BrowserPlanningUI {
         effective_history: &PlanHistory,
         result_update: &PlanResultUpdate,
         new_actions: &ActionGroups,
+        construction_cost: f64,
         _world: &mut World,
     ) {
         use ::planning::PrototypeKind;
@@ -655,6 +738,7 @@ SwitchLanePrototype, IntersectionPrototype};
                         },
                     },
                     roadInfos: {"$set": @{Serde(road_infos)}},
+                    constructionCost: {"$set": @{construction_cost}},
                 }}
             }));
         }
@@ -663,6 +747,23 @@ SwitchLanePrototype, IntersectionPrototype};
         self.actions_preview = new_actions.clone();
         self.awaiting_preview_update = false;
     }
+
+    fn on_project_conflicts(
+        &mut self,
+        project_id: ProjectID,
+        conflicts: &ProjectConflicts,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                planning: {
+                    conflicts: {
+                        [@{Serde(project_id)}]: {"$set": @{Serde(conflicts)}}
+                    }
+                }
+            }));
+        }
+    }
 }
 
 mod kay_auto;
@@ -1,17 +1,21 @@
 import React from 'react';
-import { Slider } from 'antd';
+import { Slider, Button } from 'antd';
 import update from 'immutability-helper';
 
 export const initialState = {
     ticks: 0,
     time: [0, 0],
-    speed: 1
+    date: "",
+    speed: 1,
+    paused: false,
+    warping: false
 }
 
 export function Windows(props) {
     const { state, setState } = props;
 
     return <div className="sim-time">
+        <span className="sim-date">{state.time.date}</span>
         {(state.time.time[0] + "").padStart(2, "0")}
         <span className="sim-time-colon">:</span>
         {(state.time.time[1] + "").padStart(2, "0")}
@@ -26,5 +30,9 @@ export function Windows(props) {
             }}
             tipFormatter={speed => speed ? `Speed: ${Math.pow(2, speed - 1)}x` : "Pause"}
         />
+        <Button className="sim-skip" size="small" disabled={state.time.warping}
+            onClick={() => cbRustBrowser.skip_to_next_morning()}>
+            {state.time.warping ? "Skipping..." : "Skip to morning"}
+        </Button>
     </div>
 }
\ No newline at end of file
@@ -9,7 +9,21 @@ use browser_utils::{FrameListener, FrameListenerID};
 pub fn set_sim_speed(new_speed: u16) {
     let system = unsafe { &mut *SYSTEM };
     let world = &mut system.world();
-    ::time::TimeID::global_first(world).set_speed(new_speed, world);
+    ::time::TimeID::global_first(world).set_speed(new_speed, ::session(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn step_sim(n_ticks: u32) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::time::TimeID::global_first(world).step(n_ticks, ::session(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn skip_to_next_morning() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::time::TimeID::global_first(world).skip_to_next_morning(::session(), world);
 }
 
 #[derive(Compact, Clone)]
@@ -32,7 +46,14 @@ impl FrameListener for BrowserTimeUI {
 use time::ui::{TimeUI, TimeUIID};
 
 impl TimeUI for BrowserTimeUI {
-    fn on_time_info(&mut self, current_instant: ::time::Instant, speed: u16, _world: &mut World) {
+    fn on_time_info(
+        &mut self,
+        current_instant: ::time::Instant,
+        speed: u16,
+        paused: bool,
+        warping: bool,
+        _world: &mut World,
+    ) {
         js! {
             window.cbReactApp.boundSetState(oldState => update(oldState, {
                 time: {
@@ -40,7 +61,10 @@ impl TimeUI for BrowserTimeUI {
                     time: {"$set": @{
                         Serde(::time::TimeOfDay::from(current_instant).hours_minutes())
                     }},
-                    speed: {"$set": @{speed}}
+                    date: {"$set": @{::time::Date::from(current_instant).to_string()}},
+                    speed: {"$set": @{speed}},
+                    paused: {"$set": @{paused}},
+                    warping: {"$set": @{warping}}
                 }
             }))
         }
@@ -0,0 +1,157 @@
+extern crate rouille;
+use self::rouille::{Request, Response};
+use cb_simulation::reporting::latest_report;
+use cb_simulation::control::ControlCommand;
+use cb_simulation::access::{self, SessionID};
+use cb_simulation::util::random::Uuid;
+use std::sync::Mutex;
+use std::sync::mpsc::Sender;
+
+// JSON API served next to the browser UI, or on its own when headless.
+// Reads only look at the latest city report, while /api/control hands commands over
+// to the simulation thread, so nothing here touches the actor system directly.
+
+pub type ControlSender = Mutex<Sender<ControlCommand>>;
+
+/// Serves only the JSON API and the metrics, for headless simulations without a browser UI
+pub fn start_api_server(network_config: ::init::NetworkConfig, control: ControlSender) {
+    rouille::start_server(network_config.serve_host_port.clone(), move |request| {
+        serve(request, &network_config, &control).unwrap_or_else(|| {
+            Response::text("Running headless, only /api/ and /metrics are served")
+                .with_status_code(404)
+        })
+    });
+}
+
+/// Answers requests for the JSON API and the metrics, `None` for all other requests
+pub fn serve(
+    request: &Request,
+    network_config: &::init::NetworkConfig,
+    control: &ControlSender,
+) -> Option<Response> {
+    let is_api_request = request.url() == "/metrics" || request.url().starts_with("/api/");
+
+    // the control API checks for its token by itself
+    if is_api_request
+        && request.url() != "/api/control"
+        && network_config.requires_join()
+        && !may_read(request, network_config)
+    {
+        Some(
+            Response::text("Send the API token or the session you joined with as bearer token")
+                .with_status_code(401),
+        )
+    } else if request.url() == "/metrics" {
+        Some(Response::from_data(
+            "text/plain; version=0.0.4",
+            ::metrics::render_metrics(),
+        ))
+    } else if request.url().starts_with("/api/") {
+        Some(handle_api_request(
+            request,
+            network_config.api_token.as_ref().map(|token| token.as_str()),
+            control,
+        ))
+    } else {
+        None
+    }
+}
+
+// Without passwords, everybody may read. Otherwise, only clients that joined
+// or that know the API token may read.
+fn may_read(request: &Request, network_config: &::init::NetworkConfig) -> bool {
+    let bearer_token = match request.header("Authorization") {
+        Some(header) if header.starts_with("Bearer ") => &header["Bearer ".len()..],
+        _ => return false,
+    };
+
+    let is_api_token = network_config.api_token.as_ref().map_or(false, |api_token| {
+        constant_time_eq(bearer_token.as_bytes(), api_token.as_bytes())
+    });
+
+    is_api_token
+        || Uuid::parse_str(bearer_token)
+            .ok()
+            .map_or(false, |uuid| access::role_of(SessionID(uuid)).is_some())
+}
+
+pub fn handle_api_request(
+    request: &Request,
+    api_token: Option<&str>,
+    control: &ControlSender,
+) -> Response {
+    if request.url() == "/api/control" {
+        return handle_control_request(request, api_token, control);
+    }
+
+    if request.method() != "GET" {
+        return Response::text("Only GET is supported").with_status_code(405);
+    }
+
+    let report = latest_report();
+
+    match request.url().as_str() {
+        "/api/report" => Response::json(&report),
+        "/api/time" => Response::json(&report.time),
+        "/api/counts" => Response::json(&report.counts_summary()),
+        "/api/market" => Response::json(&report.market_offers),
+        "/api/treasury" => Response::json(&report.treasury),
+        "/api/log" => {
+            let limit = request
+                .get_param("limit")
+                .and_then(|limit| limit.parse::<usize>().ok())
+                .unwrap_or_else(|| report.recent_log.len());
+            let skip = report.recent_log.len().saturating_sub(limit);
+            Response::json(&report.recent_log[skip..].to_vec())
+        }
+        url => Response::text(format!("Unknown API endpoint: {}", url)).with_status_code(404),
+    }
+}
+
+fn handle_control_request(
+    request: &Request,
+    api_token: Option<&str>,
+    control: &ControlSender,
+) -> Response {
+    if request.method() != "POST" {
+        return Response::text("Only POST is supported").with_status_code(405);
+    }
+
+    let api_token = match api_token {
+        Some(api_token) => api_token,
+        None => {
+            return Response::text("The control API is disabled, start with --api-token")
+                .with_status_code(403)
+        }
+    };
+
+    let authorized = request.header("Authorization").map_or(false, |header| {
+        constant_time_eq(header.as_bytes(), format!("Bearer {}", api_token).as_bytes())
+    });
+
+    if !authorized {
+        return Response::text("Missing or wrong API token").with_status_code(401);
+    }
+
+    match rouille::input::json_input::<ControlCommand>(request) {
+        Ok(command) => {
+            let sent = control
+                .lock()
+                .expect("control sender lock shouldn't be poisoned")
+                .send(command);
+
+            match sent {
+                Ok(()) => Response::text("Accepted").with_status_code(202),
+                Err(_) => Response::text("The simulation isn't running").with_status_code(503),
+            }
+        }
+        Err(err) => {
+            Response::text(format!("Invalid control command: {}", err)).with_status_code(400)
+        }
+    }
+}
+
+/// Looks at every byte, so response times don't tell how much of a guessed secret was right
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |difference, (a, b)| difference | (a ^ b)) == 0
+}
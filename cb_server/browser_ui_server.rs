@@ -1,14 +1,27 @@
 extern crate rouille;
-use self::rouille::{Response, extension_to_mime};
+use self::rouille::{Request, Response, extension_to_mime};
+use cb_simulation::access::{self, Role, PlayerID};
 
 #[derive(RustEmbed)]
 #[folder = "cb_browser_ui/dist/"]
 struct Asset;
 
-pub fn start_browser_ui_server(version: &'static str, network_config: ::init::NetworkConfig) {
+pub fn start_browser_ui_server(
+    version: &'static str,
+    network_config: ::init::NetworkConfig,
+    control: ::api::ControlSender,
+) {
     rouille::start_server(network_config.serve_host_port.clone(), move |request| {
-        if request.raw_url() == "/" {
-            println!("{:?} loaded page", request.remote_addr());
+        if let Some(response) = ::api::serve(request, &network_config, &control) {
+            response
+        } else if request.url() == "/" {
+            let (role, name) = match join(request, &network_config) {
+                Ok(joined) => joined,
+                Err(join_page) => return join_page,
+            };
+            let player = PlayerID::named(&name);
+            let session = access::open_session(role, player);
+            println!("{:?} loaded page as {} {}", request.remote_addr(), role.name(), name);
 
             let template = ::std::str::from_utf8(
                 &Asset::get("index.html").expect("index.html should exist as asset"),
@@ -18,6 +31,9 @@ pub fn start_browser_ui_server(version: &'static str, network_config: ::init::Ne
 
             let rendered = template
                 .replace("CB_VERSION", version.trim())
+                .replace("CB_SESSION", &session.0.to_string())
+                .replace("CB_ROLE", role.name())
+                .replace("CB_PLAYER", &player.0.to_string())
                 .replace(
                     "CB_BATCH_MESSAGE_BYTES",
                     &format!("{}", network_config.batch_msg_bytes),
@@ -31,7 +47,14 @@ pub fn start_browser_ui_server(version: &'static str, network_config: ::init::Ne
                     &format!("{}", network_config.skip_ratio),
                 );
 
-            Response::html(rendered)
+            Response::html(rendered).with_additional_header(
+                "Set-Cookie",
+                format!(
+                    "{}={}; Max-Age=31536000; Path=/; SameSite=Strict",
+                    PLAYER_NAME_COOKIE,
+                    name.replace(' ', "+")
+                ),
+            )
         } else if let Some(asset) = Asset::get(&request.url()[1..]) {
             Response::from_data(
                 if request.url().ends_with(".wasm") {
@@ -46,3 +69,118 @@ pub fn start_browser_ui_server(version: &'static str, network_config: ::init::Ne
         }
     });
 }
+
+// remembers the name a browser last joined with, so it can join as the same player again
+const PLAYER_NAME_COOKIE: &str = "cb_player_name";
+const MAX_PLAYER_NAME_CHARS: usize = 32;
+
+// only characters that can be put into the cookie and the join page as they are,
+// except for spaces, which are stored as + in the cookie
+fn is_valid_player_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().count() <= MAX_PLAYER_NAME_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' || c == '.')
+}
+
+// Clients join with a player name, which is all they need without any passwords configured.
+// Browsers remember the name, so in that case reloading joins again right away.
+// With passwords configured, clients post one with the join form and get the matching role.
+// If no spectator password is configured, joining with an empty password makes them
+// a spectator. Returns the role and the player name.
+fn join(
+    request: &Request,
+    network_config: &::init::NetworkConfig,
+) -> Result<(Role, String), Response> {
+    let spectating_is_open = network_config.spectator_password.is_none();
+    let ask_password = network_config.requires_join();
+
+    let remembered_name = rouille::input::cookies(request)
+        .find(|&(name, _)| name == PLAYER_NAME_COOKIE)
+        .map(|(_, value)| value.replace('+', " "))
+        .filter(|name| is_valid_player_name(name))
+        .unwrap_or_default();
+
+    if request.method() != "POST" {
+        if !ask_password && !remembered_name.is_empty() {
+            return Ok((Role::Admin, remembered_name));
+        }
+        return Err(join_page("", &remembered_name, ask_password, spectating_is_open));
+    }
+
+    let fields = rouille::input::post::raw_urlencoded_post_input(request).unwrap_or_default();
+    let field = |wanted: &str| {
+        fields
+            .iter()
+            .find(|&&(ref name, _)| name == wanted)
+            .map(|&(_, ref value)| value.clone())
+            .unwrap_or_default()
+    };
+
+    let name = field("name").trim().to_owned();
+    if !is_valid_player_name(&name) {
+        return Err(join_page(
+            &format!(
+                "Please enter a name of up to {} letters, digits, spaces, dashes, \
+                 underscores or dots.",
+                MAX_PLAYER_NAME_CHARS
+            ),
+            &remembered_name,
+            ask_password,
+            spectating_is_open,
+        ));
+    }
+
+    if !ask_password {
+        return Ok((Role::Admin, name));
+    }
+
+    let password = field("password");
+
+    let is = |configured: &Option<String>| {
+        configured.as_ref().map_or(false, |configured| {
+            ::api::constant_time_eq(configured.as_bytes(), password.as_bytes())
+        })
+    };
+
+    if is(&network_config.admin_password) {
+        Ok((Role::Admin, name))
+    } else if is(&network_config.planner_password) {
+        Ok((Role::Planner, name))
+    } else if is(&network_config.spectator_password)
+        || (spectating_is_open && password.is_empty())
+    {
+        Ok((Role::Spectator, name))
+    } else {
+        Err(join_page("Wrong password.", &name, ask_password, spectating_is_open))
+    }
+}
+
+// `name` has to be a valid player name or empty, it is put into the page as it is
+fn join_page(message: &str, name: &str, ask_password: bool, spectating_is_open: bool) -> Response {
+    let password_input = if ask_password {
+        "<input type=\"password\" name=\"password\" placeholder=\"Password\">"
+    } else {
+        ""
+    };
+
+    let hint = if ask_password && spectating_is_open {
+        "Leave the password empty to join as a spectator."
+    } else {
+        ""
+    };
+
+    Response::html(format!(
+        "<html><head><title>Citybound</title></head><body>\
+         <p>{}</p>\
+         <form method=\"post\" action=\"/\">\
+         <input type=\"text\" name=\"name\" value=\"{}\" placeholder=\"Player name\" \
+         autocomplete=\"username\" autofocus>\
+         {}\
+         <button type=\"submit\">Join</button>\
+         </form><p>{}</p></body></html>",
+        message, name, password_input, hint
+    ))
+    .with_status_code(401)
+}
@@ -1,8 +1,12 @@
 extern crate open;
 extern crate backtrace;
 extern crate clap;
+extern crate toml;
+extern crate ctrlc;
 
 use std::time::{Instant, Duration};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::str::FromStr;
 
 pub fn print_start_message(version: &str, network_config: &NetworkConfig) {
     let my_host = format!(
@@ -34,14 +38,171 @@ pub struct NetworkConfig {
     pub batch_msg_bytes: usize,
     pub ok_turn_dist: usize,
     pub skip_ratio: usize,
+    pub api_token: Option<String>,
+    pub admin_password: Option<String>,
+    pub planner_password: Option<String>,
+    pub spectator_password: Option<String>,
 }
 
-pub fn match_cmd_line_args(version: &str) -> NetworkConfig {
+impl NetworkConfig {
+    /// Whether clients have to join with a password before they get a session
+    pub fn requires_join(&self) -> bool {
+        self.admin_password.is_some()
+            || self.planner_password.is_some()
+            || self.spectator_password.is_some()
+    }
+}
+
+#[derive(Clone)]
+pub struct SavegameConfig {
+    pub load_path: Option<String>,
+    pub save_path: Option<String>,
+    pub autosave_interval_minutes: Option<usize>,
+    pub autosave_dir: String,
+    pub autosave_keep: usize,
+}
+
+#[derive(Clone)]
+pub struct PlanFilesConfig {
+    pub import_path: Option<String>,
+    pub export_path: Option<String>,
+    pub osm_path: Option<String>,
+    pub geojson_path: Option<String>,
+    pub journal_path: Option<String>,
+    pub replay_journal_path: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct LogConfig {
+    pub max_entries: usize,
+    pub file_dir: Option<String>,
+    pub file_max_bytes: u64,
+    pub files_keep: usize,
+}
+
+#[derive(Clone)]
+pub struct HeadlessConfig {
+    pub stop_after_minutes: Option<usize>,
+    pub stop_at_ticks: Option<usize>,
+    pub serve_api: bool,
+}
+
+#[derive(Clone)]
+pub struct ServerConfig {
+    pub network: NetworkConfig,
+    pub savegame: SavegameConfig,
+    pub plan_files: PlanFilesConfig,
+    pub log: LogConfig,
+    pub headless: Option<HeadlessConfig>,
+    pub seed: Option<u64>,
+    pub scenario_path: Option<String>,
+    pub tuning: ::cb_simulation::tuning::Tuning,
+}
+
+// Everything in a config file is optional, command line arguments take precedence
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    network: NetworkConfigFile,
+    tuning: toml::value::Table,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+struct NetworkConfigFile {
+    batch_msg_bytes: Option<usize>,
+    ok_turn_dist: Option<usize>,
+    skip_ratio: Option<usize>,
+    admin_password: Option<String>,
+    planner_password: Option<String>,
+    spectator_password: Option<String>,
+}
+
+fn read_config_file(path: &str) -> Result<ConfigFile, String> {
+    let mut content = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut content))
+        .map_err(|err| err.to_string())?;
+    toml::from_str(&content).map_err(|err| err.to_string())
+}
+
+// Applies a `key=value` override, where value is written like in the config file
+fn apply_tune_arg(tuning: &mut toml::value::Table, arg: &str) -> Result<(), String> {
+    let mut key_and_value = arg.splitn(2, '=');
+    let key = key_and_value.next().unwrap_or("").trim();
+    let value = key_and_value
+        .next()
+        .ok_or_else(|| format!("Expected key=value, got {}", arg))?;
+    let value: toml::Value = toml::from_str(&format!("value = {}", value))
+        .map_err(|err| err.to_string())
+        .and_then(|mut parsed: toml::value::Table| {
+            parsed.remove("value").ok_or_else(|| "Missing value".to_owned())
+        })?;
+    tuning.insert(key.to_owned(), value);
+    Ok(())
+}
+
+// Numeric arguments are validated by clap, so invalid ones are reported like an unknown --mode
+fn is_number<T: FromStr>(value: String) -> Result<(), String> {
+    value
+        .parse::<T>()
+        .map(|_| ())
+        .map_err(|_| format!("{} is not a valid number", value))
+}
+
+fn is_positive_number(value: String) -> Result<(), String> {
+    match value.parse::<usize>() {
+        Ok(0) => Err("has to be at least 1".to_owned()),
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("{} is not a valid number", value)),
+    }
+}
+
+// only for arguments validated by `is_number` or `is_positive_number`
+fn number_of<T: FromStr>(matches: &clap::ArgMatches, name: &str) -> Option<T> {
+    matches.value_of(name).map(|value| {
+        value
+            .parse()
+            .ok()
+            .expect("clap should have validated the number")
+    })
+}
+
+fn exit_with_invalid_value(message: &str) -> ! {
+    clap::Error::with_description(message, clap::ErrorKind::InvalidValue).exit()
+}
+
+// next to the savegame, so autosaves of different cities don't rotate each other away
+fn default_autosave_dir(savegame_path: Option<&str>) -> String {
+    match savegame_path {
+        Some(path) => ::std::path::Path::new(path)
+            .with_extension("autosaves")
+            .to_string_lossy()
+            .into_owned(),
+        None => "autosaves".to_owned(),
+    }
+}
+
+pub fn match_cmd_line_args(version: &str) -> ServerConfig {
     use self::clap::{Arg, App};
     let matches = App::new("citybound")
         .version(version.trim())
         .author("ae play (Anselm Eickhoff)")
         .about("The city is us.")
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("citybound.toml")
+                .help("Config file with network settings and gameplay tuning"),
+        )
+        .arg(
+            Arg::with_name("tune")
+                .long("tune")
+                .value_name("key=value")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Overrides a gameplay tuning value of the config file"),
+        )
         .arg(
             Arg::with_name("mode")
                 .long("mode")
@@ -78,6 +239,7 @@ pub fn match_cmd_line_args(version: &str) -> NetworkConfig {
                 .long("batch-msg-bytes")
                 .value_name("n-bytes")
                 .default_value("5000")
+                .validator(is_number::<usize>)
                 .help("How many bytes of simulation messages to batch"),
         )
         .arg(
@@ -89,6 +251,7 @@ pub fn match_cmd_line_args(version: &str) -> NetworkConfig {
                     ("mode", Some("lan"), "10"),
                     ("mode", Some("internet"), "30"),
                 ])
+                .validator(is_number::<usize>)
                 .help("How many network turns client/server can be behind before skipping"),
         )
         .arg(
@@ -96,17 +259,268 @@ pub fn match_cmd_line_args(version: &str) -> NetworkConfig {
                 .long("skip-ratio")
                 .value_name("n-turns")
                 .default_value("5")
+                .validator(is_number::<usize>)
                 .help("How many network turns to skip if server/client are ahead"),
         )
+        .arg(
+            Arg::with_name("api-token")
+                .long("api-token")
+                .value_name("token")
+                .help("Enables the HTTP control API for requests bearing this token"),
+        )
+        .arg(
+            Arg::with_name("admin-password")
+                .long("admin-password")
+                .value_name("password")
+                .help("Password to join as admin, who may also control time"),
+        )
+        .arg(
+            Arg::with_name("planner-password")
+                .long("planner-password")
+                .value_name("password")
+                .help("Password to join as planner, who may edit and implement projects"),
+        )
+        .arg(
+            Arg::with_name("spectator-password")
+                .long("spectator-password")
+                .value_name("password")
+                .help("Password to join as spectator. Without it, anyone may spectate"),
+        )
+        .arg(
+            Arg::with_name("load")
+                .long("load")
+                .value_name("file.cbsave")
+                .help("Savegame to continue from instead of starting a new city"),
+        )
+        .arg(
+            Arg::with_name("save")
+                .long("save")
+                .value_name("file.cbsave")
+                .help("Where to save the city on shutdown. Without it, the city isn't saved"),
+        )
+        .arg(
+            Arg::with_name("autosave-every")
+                .long("autosave-every")
+                .value_name("n-sim-minutes")
+                .validator(is_positive_number)
+                .help("Autosave every n simulated minutes. Without it, there are no autosaves"),
+        )
+        .arg(
+            Arg::with_name("autosave-dir")
+                .long("autosave-dir")
+                .value_name("directory")
+                .help(
+                    "Directory to write autosaves to. Defaults to one named after the savegame, \
+                     like city.autosaves for city.cbsave, or autosaves without a savegame",
+                ),
+        )
+        .arg(
+            Arg::with_name("autosave-keep")
+                .long("autosave-keep")
+                .value_name("n-files")
+                .default_value("5")
+                .validator(is_number::<usize>)
+                .help("How many of the most recent autosaves to keep"),
+        )
+        .arg(
+            Arg::with_name("import-plan")
+                .long("import-plan")
+                .value_name("plan.json")
+                .help("Plan file to implement as a new project on startup"),
+        )
+        .arg(
+            Arg::with_name("export-plan")
+                .long("export-plan")
+                .value_name("plan.json")
+                .help("Where to export the master plan to on shutdown"),
+        )
+        .arg(
+            Arg::with_name("import-osm")
+                .long("import-osm")
+                .value_name("map.osm")
+                .help("OpenStreetMap XML extract to import as a new project on startup"),
+        )
+        .arg(
+            Arg::with_name("export-geojson")
+                .long("export-geojson")
+                .value_name("city.geojson")
+                .help("Where to export the built city as GeoJSON to on shutdown"),
+        )
+        .arg(
+            Arg::with_name("journal")
+                .long("journal")
+                .value_name("journal.jsonl")
+                .help("Append every change to the plans to this journal"),
+        )
+        .arg(
+            Arg::with_name("replay-journal")
+                .long("replay-journal")
+                .value_name("journal.jsonl")
+                .help("Replay all plan changes of a journal at their recorded times"),
+        )
+        .arg(
+            Arg::with_name("log-max-entries")
+                .long("log-max-entries")
+                .value_name("n-entries")
+                .default_value("100000")
+                .validator(is_number::<usize>)
+                .help("How many of the newest simulation log entries to keep in memory"),
+        )
+        .arg(
+            Arg::with_name("log-dir")
+                .long("log-dir")
+                .value_name("directory")
+                .help("Stream the simulation log to rotating JSON-lines files in this directory"),
+        )
+        .arg(
+            Arg::with_name("log-file-mb")
+                .long("log-file-mb")
+                .value_name("megabytes")
+                .default_value("16")
+                .validator(is_positive_number)
+                .help("How big a log file can get before a new one is started"),
+        )
+        .arg(
+            Arg::with_name("log-files-keep")
+                .long("log-files-keep")
+                .value_name("n-files")
+                .default_value("10")
+                .validator(is_number::<usize>)
+                .help("How many of the most recent log files to keep"),
+        )
+        .arg(
+            Arg::with_name("headless")
+                .long("headless")
+                .help("Simulate as fast as possible without serving the browser UI"),
+        )
+        .arg(
+            Arg::with_name("headless-api")
+                .long("headless-api")
+                .requires("headless")
+                .help("Serve the JSON API and metrics while running headless"),
+        )
+        .arg(
+            Arg::with_name("stop-after")
+                .long("stop-after")
+                .value_name("n-sim-minutes")
+                .requires("headless")
+                .validator(is_number::<usize>)
+                .help("Stop a headless simulation after this much simulated time"),
+        )
+        .arg(
+            Arg::with_name("stop-at")
+                .long("stop-at")
+                .value_name("n-ticks")
+                .requires("headless")
+                .validator(is_number::<usize>)
+                .help("Stop a headless simulation once it reaches this instant"),
+        )
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .value_name("n")
+                .validator(is_number::<u64>)
+                .help("Seed all randomness, making runs with the same inputs reproducible"),
+        )
+        .arg(
+            Arg::with_name("scenario")
+                .long("scenario")
+                .value_name("scenario.json")
+                .help("Scenario with projects and timed events to play out"),
+        )
         .get_matches();
 
-    NetworkConfig {
-        serve_host_port: matches.value_of("bind").unwrap().to_owned(),
-        bind_sim: matches.value_of("bind-sim").unwrap().to_owned(),
-        mode: matches.value_of("mode").unwrap().to_owned(),
-        batch_msg_bytes: matches.value_of("batch-msg-b").unwrap().parse().unwrap(),
-        ok_turn_dist: matches.value_of("ok-turn-dist").unwrap().parse().unwrap(),
-        skip_ratio: matches.value_of("skip-ratio").unwrap().parse().unwrap(),
+    let config_file = matches.value_of("config").map_or_else(ConfigFile::default, |path| {
+        read_config_file(path).unwrap_or_else(|err| {
+            exit_with_invalid_value(&format!("Couldn't read config {}: {}", path, err))
+        })
+    });
+
+    // explicitly given arguments win over the config file, which wins over defaults
+    let network_setting = |name: &str, from_config_file: Option<usize>| -> usize {
+        let from_args = number_of(&matches, name).expect("network settings have defaults");
+        if matches.occurrences_of(name) > 0 {
+            from_args
+        } else {
+            from_config_file.unwrap_or(from_args)
+        }
+    };
+
+    // an empty password would let everybody in, so it counts as not configured
+    let password = |name: &str, from_config_file: Option<String>| -> Option<String> {
+        matches
+            .value_of(name)
+            .map(|password| password.to_owned())
+            .or(from_config_file)
+            .filter(|password| !password.is_empty())
+    };
+
+    let mut tuning_table = config_file.tuning;
+    for tune_arg in matches.values_of("tune").into_iter().flatten() {
+        apply_tune_arg(&mut tuning_table, tune_arg).unwrap_or_else(|err| {
+            exit_with_invalid_value(&format!("Invalid --tune {}: {}", tune_arg, err))
+        });
+    }
+    let tuning = toml::Value::Table(tuning_table)
+        .try_into()
+        .unwrap_or_else(|err| exit_with_invalid_value(&format!("Invalid tuning: {}", err)));
+
+    ServerConfig {
+        network: NetworkConfig {
+            serve_host_port: matches.value_of("bind").unwrap().to_owned(),
+            bind_sim: matches.value_of("bind-sim").unwrap().to_owned(),
+            mode: matches.value_of("mode").unwrap().to_owned(),
+            batch_msg_bytes: network_setting("batch-msg-b", config_file.network.batch_msg_bytes),
+            ok_turn_dist: network_setting("ok-turn-dist", config_file.network.ok_turn_dist),
+            skip_ratio: network_setting("skip-ratio", config_file.network.skip_ratio),
+            api_token: matches.value_of("api-token").map(|token| token.to_owned()),
+            admin_password: password("admin-password", config_file.network.admin_password),
+            planner_password: password("planner-password", config_file.network.planner_password),
+            spectator_password: password(
+                "spectator-password",
+                config_file.network.spectator_password,
+            ),
+        },
+        savegame: SavegameConfig {
+            save_path: matches.value_of("save").map(|path| path.to_owned()),
+            load_path: matches.value_of("load").map(|path| path.to_owned()),
+            autosave_interval_minutes: number_of(&matches, "autosave-every"),
+            autosave_dir: matches
+                .value_of("autosave-dir")
+                .map(|dir| dir.to_owned())
+                .unwrap_or_else(|| {
+                    default_autosave_dir(matches.value_of("save").or(matches.value_of("load")))
+                }),
+            autosave_keep: number_of(&matches, "autosave-keep").expect("has a default"),
+        },
+        plan_files: PlanFilesConfig {
+            import_path: matches.value_of("import-plan").map(|path| path.to_owned()),
+            export_path: matches.value_of("export-plan").map(|path| path.to_owned()),
+            osm_path: matches.value_of("import-osm").map(|path| path.to_owned()),
+            geojson_path: matches.value_of("export-geojson").map(|path| path.to_owned()),
+            journal_path: matches.value_of("journal").map(|path| path.to_owned()),
+            replay_journal_path: matches.value_of("replay-journal").map(|path| path.to_owned()),
+        },
+        log: LogConfig {
+            max_entries: number_of(&matches, "log-max-entries").expect("has a default"),
+            file_dir: matches.value_of("log-dir").map(|path| path.to_owned()),
+            file_max_bytes: number_of::<u64>(&matches, "log-file-mb").expect("has a default")
+                * 1024
+                * 1024,
+            files_keep: number_of(&matches, "log-files-keep").expect("has a default"),
+        },
+        headless: if matches.is_present("headless") {
+            Some(HeadlessConfig {
+                stop_after_minutes: number_of(&matches, "stop-after"),
+                stop_at_ticks: number_of(&matches, "stop-at"),
+                serve_api: matches.is_present("headless-api"),
+            })
+        } else {
+            None
+        },
+        seed: number_of(&matches, "seed"),
+        scenario_path: matches.value_of("scenario").map(|path| path.to_owned()),
+        tuning,
     }
 }
 
@@ -129,7 +543,7 @@ pub fn ensure_crossplatform_proper_thread<F: Fn() -> () + Send + 'static>(callba
 use std::panic::{set_hook, PanicInfo};
 use self::backtrace::Backtrace;
 use std::fs::File;
-use std::io::Write;
+use std::io::{Read, Write};
 
 pub fn set_error_hook() {
     let callback: Box<FnMut(&PanicInfo)> = Box::new(move |panic_info| {
@@ -183,6 +597,39 @@ pub fn set_error_hook() {
     set_hook(unsafe { ::std::mem::transmute(callback) });
 }
 
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Makes Ctrl-C/SIGTERM request a graceful shutdown, a second one exits immediately
+pub fn set_shutdown_handler() {
+    ctrlc::set_handler(|| {
+        if SHUTDOWN_REQUESTED.swap(true, Ordering::SeqCst) {
+            println!("Exiting without shutting down gracefully");
+            ::std::process::exit(130);
+        } else {
+            println!("Shutting down, interrupt again to exit immediately");
+        }
+    })
+    .expect("Couldn't set shutdown handler");
+}
+
+pub fn shutdown_requested() -> bool {
+    SHUTDOWN_REQUESTED.load(Ordering::SeqCst)
+}
+
+// messages can cause new messages, but without time progressing this settles quickly
+const MAX_DRAIN_ROUNDS: usize = 100;
+
+/// Handles messages until all queues are empty, so no actor is left in between states
+pub fn drain_messages(system: &mut ::cb_simulation::kay::ActorSystem) {
+    for _ in 0..MAX_DRAIN_ROUNDS {
+        system.process_all_messages();
+        if system.get_queue_lengths().values().all(|&length| length == 0) {
+            return;
+        }
+    }
+    println!("Messages still queued after {} rounds, shutting down anyway", MAX_DRAIN_ROUNDS);
+}
+
 pub struct FrameCounter {
     last_frame: Instant,
     elapsed_ms_collected: Vec<f32>,
@@ -209,6 +656,16 @@ impl FrameCounter {
         self.last_frame = Instant::now();
     }
 
+    pub fn fps(&self) -> f32 {
+        if self.elapsed_ms_collected.is_empty() {
+            return 0.0;
+        }
+
+        let average_elapsed_ms = self.elapsed_ms_collected.iter().sum::<f32>()
+            / self.elapsed_ms_collected.len() as f32;
+        1000.0 / average_elapsed_ms
+    }
+
     pub fn sleep_if_faster_than(&self, fps: usize) {
         let ideal_frame_duration = Duration::from_millis((1000.0 / (fps as f32)) as u64);
 
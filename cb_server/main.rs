@@ -3,26 +3,75 @@ use cb_simulation::*;
 
 #[macro_use]
 extern crate rust_embed_flag;
+#[macro_use]
+extern crate serde_derive;
 
 const VERSION: &str = include_str!("../.version");
+// how often the city report behind the JSON API and metrics is refreshed
+const REPORT_EVERY_N_FRAMES: usize = 30;
 
 mod init;
 mod browser_ui_server;
+mod api;
+mod metrics;
 
 fn main() {
-    let network_config = init::match_cmd_line_args(VERSION);
+    let server_config = init::match_cmd_line_args(VERSION);
+    let network_config = server_config.network.clone();
+    let savegame_config = server_config.savegame.clone();
+    let plan_files_config = server_config.plan_files.clone();
+    let log_config = server_config.log.clone();
+    let headless_config = server_config.headless.clone();
+    let scenario_path = server_config.scenario_path.clone();
+
+    tuning::set_tuning(&server_config.tuning);
+    log::set_max_entries(log_config.max_entries);
+    init::set_shutdown_handler();
+
+    if network_config.requires_join() {
+        access::require_sessions();
+    }
+
+    if let Some(seed) = server_config.seed {
+        util::random::set_global_seed(seed);
+    }
+
+    // commands from the control API, applied in between frames of the simulation thread
+    let (control_sender, control_receiver) = ::std::sync::mpsc::channel();
+    let control_sender = ::std::sync::Mutex::new(control_sender);
+
+    let network_addresses = if let Some(ref headless_config) = headless_config {
+        if headless_config.serve_api {
+            println!(
+                "Citybound {} running headless, serving the API at http://{}/api/",
+                VERSION.trim(),
+                network_config.serve_host_port
+            );
+
+            let network_config_2 = network_config.clone();
+            ::std::thread::spawn(move || {
+                api::start_api_server(network_config_2, control_sender);
+            });
+        } else {
+            println!("Citybound {} running headless", VERSION.trim());
+        }
 
-    init::print_start_message(VERSION, &network_config);
+        vec![network_config.bind_sim.clone()]
+    } else {
+        init::print_start_message(VERSION, &network_config);
 
-    let network_config_2 = network_config.clone();
-    ::std::thread::spawn(move || {
-        browser_ui_server::start_browser_ui_server(VERSION, network_config_2);
-    });
+        let network_config_2 = network_config.clone();
+        ::std::thread::spawn(move || {
+            browser_ui_server::start_browser_ui_server(VERSION, network_config_2, control_sender);
+        });
+
+        vec![network_config.bind_sim.clone(), "ws-client".to_owned()]
+    };
 
     init::ensure_crossplatform_proper_thread(move || {
         let mut system = Box::new(kay::ActorSystem::new(kay::Networking::new(
             0,
-            vec![network_config.bind_sim.clone(), "ws-client".to_owned()],
+            network_addresses,
             network_config.batch_msg_bytes,
             network_config.ok_turn_dist,
             network_config.skip_ratio,
@@ -34,30 +83,150 @@ fn main() {
 
         let world = &mut system.world();
 
-        log::spawn(world);
-        let time = time::spawn(world);
-        let plan_manager = planning::spawn(world);
-        construction::spawn(world);
-        transport::spawn(world, time);
-        economy::spawn(world, time, plan_manager);
-        environment::vegetation::spawn(world, plan_manager);
+        if let Some(ref log_dir) = log_config.file_dir {
+            log::file_sink::start(log_dir, log_config.file_max_bytes, log_config.files_keep)
+                .unwrap_or_else(|err| panic!("Couldn't start logging to {}: {}", log_dir, err));
+        }
+
+        let (time, plan_manager) = if let Some(ref load_path) = savegame_config.load_path {
+            let n_restored = persistence::load(load_path, world)
+                .unwrap_or_else(|err| panic!("Couldn't load savegame {}: {}", load_path, err));
+            system.process_all_messages();
+            println!("Restored {} actors from {}", n_restored, load_path);
+            (
+                time::TimeID::local_first(world),
+                planning::PlanManagerID::local_first(world),
+            )
+        } else {
+            log::spawn(world);
+            let time = time::spawn(world);
+            let plan_manager = planning::spawn(world);
+            construction::spawn(world);
+            transport::spawn(world, time);
+            economy::spawn(world, time, plan_manager);
+            environment::vegetation::spawn(world, plan_manager);
+            system.process_all_messages();
+            (time, plan_manager)
+        };
+
+        if let Some(ref journal_path) = plan_files_config.journal_path {
+            planning::journal::start(journal_path, plan_manager, world)
+                .unwrap_or_else(|err| panic!("Couldn't open journal {}: {}", journal_path, err));
+            system.process_all_messages();
+        }
+
+        if let Some(ref replay_journal_path) = plan_files_config.replay_journal_path {
+            planning::journal::replay(replay_journal_path, time, plan_manager, world)
+                .unwrap_or_else(|err| {
+                    panic!("Couldn't read journal {}: {}", replay_journal_path, err)
+                });
+            system.process_all_messages();
+        }
+
+        if let Some(ref import_path) = plan_files_config.import_path {
+            plan_manager.import_plan(import_path.clone().into(), access::server_session(), world);
+            system.process_all_messages();
+        }
+
+        if let Some(ref osm_path) = plan_files_config.osm_path {
+            plan_manager.import_osm(osm_path.clone().into(), access::server_session(), world);
+            system.process_all_messages();
+        }
+
+        if let Some(ref scenario_path) = scenario_path {
+            let scenario = scenario::Scenario::read(scenario_path)
+                .unwrap_or_else(|err| panic!("Couldn't read scenario {}: {}", scenario_path, err));
+            scenario::start(scenario, time::latest_instant(), time, plan_manager, world);
+            system.process_all_messages();
+        }
+
+        if let Some(interval_minutes) = savegame_config.autosave_interval_minutes {
+            persistence::autosave::spawn(
+                world,
+                time,
+                time::Duration::from_minutes(interval_minutes),
+            );
+            system.process_all_messages();
+        }
+
+        let city_reporter = reporting::spawn(world);
         system.process_all_messages();
 
         let mut frame_counter = init::FrameCounter::new();
         let mut skip_turns = 0;
+        let mut frames_since_report = 0;
+        let mut headless_started_at = None;
 
         loop {
             frame_counter.start_frame();
 
             system.process_all_messages();
 
-            if system.shutting_down {
+            while let Ok(command) = control_receiver.try_recv() {
+                control::apply(command, time, plan_manager, world);
+            }
+            system.process_all_messages();
+
+            let headless_done = headless_config.as_ref().map_or(false, |headless_config| {
+                let now = time::latest_instant();
+                let stop_after_reached =
+                    match (headless_started_at, headless_config.stop_after_minutes) {
+                        (Some(started_at), Some(minutes)) => {
+                            now >= started_at + time::Duration::from_minutes(minutes)
+                        }
+                        _ => false,
+                    };
+                let stop_at_reached = headless_config
+                    .stop_at_ticks
+                    .map_or(false, |ticks| now >= time::Instant::new(ticks));
+                stop_after_reached || stop_at_reached
+            });
+
+            if system.shutting_down || headless_done || init::shutdown_requested() {
+                init::drain_messages(&mut system);
+                if let Some(ref export_path) = plan_files_config.export_path {
+                    plan_manager.export_master_plan(
+                        export_path.clone().into(),
+                        access::server_session(),
+                        world,
+                    );
+                    system.process_all_messages();
+                }
+                if let Some(ref geojson_path) = plan_files_config.geojson_path {
+                    plan_manager.export_geojson(
+                        geojson_path.clone().into(),
+                        access::server_session(),
+                        world,
+                    );
+                    system.process_all_messages();
+                }
+                if let Some(ref save_path) = savegame_config.save_path {
+                    persistence::save(&mut system, save_path);
+                }
                 break;
             }
 
             if skip_turns == 0 {
                 time.progress(world);
                 system.process_all_messages();
+                headless_started_at.get_or_insert(time::latest_instant());
+            }
+
+            if let Some(instant) = persistence::autosave::take_due_autosave() {
+                if let Err(err) = persistence::autosave::autosave(
+                    &mut system,
+                    &savegame_config.autosave_dir,
+                    savegame_config.autosave_keep,
+                    instant,
+                ) {
+                    println!("Autosave failed: {}", err);
+                }
+            }
+
+            frames_since_report += 1;
+            if frames_since_report >= REPORT_EVERY_N_FRAMES {
+                reporting::report(&mut system, city_reporter, frame_counter.fps());
+                frames_since_report = 0;
             }
 
             system.networking_send_and_receive();
@@ -72,7 +241,9 @@ fn main() {
                 }
             }
 
-            frame_counter.sleep_if_faster_than(120);
+            if headless_config.is_none() {
+                frame_counter.sleep_if_faster_than(120);
+            }
         }
     });
 }
@@ -0,0 +1,79 @@
+use cb_simulation::reporting::{CityReport, latest_report};
+use std::fmt::Write;
+
+// Renders the latest city report in the Prometheus text exposition format.
+// Counters only count since the server process started.
+
+pub fn render_metrics() -> String {
+    let report = latest_report();
+    let mut out = String::new();
+    write_metrics(&report, &mut out).expect("writing to a string can't fail");
+    out
+}
+
+fn write_metrics(report: &CityReport, out: &mut String) -> ::std::fmt::Result {
+    header(out, "cb_fps", "gauge", "Simulation frames per second")?;
+    writeln!(out, "cb_fps {}", report.system.fps)?;
+
+    header(out, "cb_sim_ticks", "gauge", "Current simulation instant in ticks")?;
+    writeln!(out, "cb_sim_ticks {}", report.time.ticks)?;
+
+    header(out, "cb_queue_length", "gauge", "Messages waiting in each actor type's inbox")?;
+    for (actor_type, length) in &report.system.queue_lengths {
+        writeln!(out, "cb_queue_length{{type=\"{}\"}} {}", escape(actor_type), length)?;
+    }
+
+    header(out, "cb_messages_total", "counter", "Messages handled, by message type")?;
+    for (message_type, n) in &report.system.message_statistics {
+        writeln!(out, "cb_messages_total{{type=\"{}\"}} {}", escape(message_type), n)?;
+    }
+
+    header(out, "cb_actors", "gauge", "Number of actors, by actor type")?;
+    for (kind, n) in &report.counts {
+        writeln!(out, "cb_actors{{type=\"{:?}\"}} {}", kind, n)?;
+    }
+
+    header(out, "cb_households", "gauge", "Number of households, by kind")?;
+    for (kind, n) in report.counts.iter().filter(|&(kind, _)| kind.is_household()) {
+        writeln!(out, "cb_households{{kind=\"{:?}\"}} {}", kind, n)?;
+    }
+
+    header(out, "cb_cars", "gauge", "Cars currently in microtraffic")?;
+    writeln!(out, "cb_cars {}", report.cars)?;
+
+    header(out, "cb_trips_started_total", "counter", "Trips started")?;
+    writeln!(out, "cb_trips_started_total {}", report.trips.started)?;
+
+    header(out, "cb_trips_finished_total", "counter", "Trips finished, by fate")?;
+    for &(fate, n) in &report.trips.finished_by_fate {
+        writeln!(out, "cb_trips_finished_total{{fate=\"{}\"}} {}", fate, n)?;
+    }
+
+    header(out, "cb_construction_queue_length", "gauge", "Queued construction action groups")?;
+    writeln!(out, "cb_construction_queue_length {}", report.construction_queue)?;
+
+    header(out, "cb_treasury_balance", "gauge", "Money the city government has left")?;
+    writeln!(out, "cb_treasury_balance {}", report.treasury.balance)?;
+
+    if let Some(today) = report.treasury.ledger.last() {
+        header(out, "cb_treasury_today", "gauge", "Money the city earned and spent today")?;
+        writeln!(out, "cb_treasury_today{{item=\"income_taxes\"}} {}", today.income_taxes)?;
+        writeln!(out, "cb_treasury_today{{item=\"sales_taxes\"}} {}", today.sales_taxes)?;
+        writeln!(out, "cb_treasury_today{{item=\"construction\"}} {}", today.construction)?;
+        writeln!(out, "cb_treasury_today{{item=\"maintenance\"}} {}", today.maintenance)?;
+    }
+
+    Ok(())
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) -> ::std::fmt::Result {
+    writeln!(out, "# HELP {} {}", name, help)?;
+    writeln!(out, "# TYPE {} {}", name, kind)
+}
+
+fn escape(label_value: &str) -> String {
+    label_value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
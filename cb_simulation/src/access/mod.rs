@@ -0,0 +1,166 @@
+use kay::{World, TypedID};
+use util::random::{seed, Rng, Uuid};
+use log::warn;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+const LOG_T: &str = "Access";
+
+// Clients can't be told apart from each other once they are connected, so there is no way
+// to notice when one leaves. Instead, sessions that weren't used for this long expire.
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(12 * 60 * 60);
+
+// All browser clients share one machine in the actor system and messages don't carry
+// their sender, so clients prove who they are by sending the session they joined with
+// along with every planning and time message. Sessions only live in the server process.
+
+/// What a client may do, ordered from least to most privileged
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum Role {
+    /// May only watch the city
+    Spectator,
+    /// May also edit and implement projects
+    Planner,
+    /// May also control the passing of time
+    Admin,
+}
+
+impl Role {
+    pub fn name(self) -> &'static str {
+        match self {
+            Role::Spectator => "spectator",
+            Role::Planner => "planner",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct SessionID(pub Uuid);
+
+/// Identifies the player behind a session. Unlike the session itself, it can be shown
+/// to other clients, for example as the owner of a project.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct PlayerID(pub Uuid);
+
+impl PlayerID {
+    /// Players are known by the name they join with, so the same name
+    /// is the same player again after reloading or reconnecting
+    pub fn named(name: &str) -> PlayerID {
+        PlayerID(Uuid::from_random_bytes(seed(name).gen()))
+    }
+}
+
+struct OpenSession {
+    role: Role,
+    player: PlayerID,
+    last_used: Instant,
+}
+
+impl OpenSession {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.last_used) > SESSION_IDLE_TIMEOUT
+    }
+}
+
+lazy_static! {
+    static ref SESSIONS: RwLock<HashMap<SessionID, OpenSession>> = RwLock::new(HashMap::new());
+    // never handed out to clients, used for everything the server does by itself
+    static ref SERVER_SESSION: SessionID = SessionID(Uuid::new_v4());
+    static ref CONTROL_SESSION: SessionID = SessionID(Uuid::new_v4());
+}
+
+static SESSIONS_REQUIRED: AtomicBool = AtomicBool::new(false);
+
+/// From now on, only known sessions may do what their role permits.
+/// Until this is called, every session is treated like an admin.
+pub fn require_sessions() {
+    SESSIONS_REQUIRED.store(true, Ordering::SeqCst);
+}
+
+/// Whether clients need to have joined with a password
+pub fn sessions_required() -> bool {
+    SESSIONS_REQUIRED.load(Ordering::Relaxed)
+}
+
+/// Starts a session for a client that joined as `player` with `role`
+pub fn open_session(role: Role, player: PlayerID) -> SessionID {
+    // not derived from the seeded simulation randomness, sessions need to be unguessable
+    let session = SessionID(Uuid::new_v4());
+    let now = Instant::now();
+    let mut sessions = SESSIONS.write().expect("sessions lock shouldn't be poisoned");
+    // every page load opens a session, so this is a good time to forget about old ones
+    sessions.retain(|_, open_session| !open_session.is_expired(now));
+    sessions.insert(
+        session,
+        OpenSession {
+            role,
+            player,
+            last_used: now,
+        },
+    );
+    session
+}
+
+/// The session the server uses for its own planning and time messages
+pub fn server_session() -> SessionID {
+    *SERVER_SESSION
+}
+
+/// The session commands from the control API are carried out with. Unlike the server
+/// session, it is an ordinary admin that has to pay for and wait for approval of projects.
+pub fn control_session() -> SessionID {
+    *CONTROL_SESSION
+}
+
+pub fn role_of(session: SessionID) -> Option<Role> {
+    if session == server_session() || session == control_session() || !sessions_required() {
+        Some(Role::Admin)
+    } else {
+        let now = Instant::now();
+        let mut sessions = SESSIONS.write().expect("sessions lock shouldn't be poisoned");
+        if let Some(open_session) = sessions.get_mut(&session) {
+            if !open_session.is_expired(now) {
+                open_session.last_used = now;
+                return Some(open_session.role);
+            }
+        }
+        // unknown or expired
+        sessions.remove(&session);
+        None
+    }
+}
+
+/// The player behind a session opened by a client, the server itself is nobody
+pub fn player_of(session: SessionID) -> Option<PlayerID> {
+    SESSIONS
+        .read()
+        .expect("sessions lock shouldn't be poisoned")
+        .get(&session)
+        .map(|open_session| open_session.player)
+}
+
+/// Whether `session` has at least the `needed` role, logs a warning in the name of
+/// `refused_by` otherwise
+pub fn ensure_role<I: TypedID>(
+    session: SessionID,
+    needed: Role,
+    action: &str,
+    refused_by: I,
+    world: &mut World,
+) -> bool {
+    match role_of(session) {
+        Some(role) if role >= needed => true,
+        maybe_role => {
+            let who = maybe_role.map_or("unknown", |role| role.name());
+            warn(
+                LOG_T,
+                format!("Refused to {} for {} session, needs {}", action, who, needed.name()),
+                refused_by,
+                world,
+            );
+            false
+        }
+    }
+}
@@ -109,6 +109,10 @@ impl ConstructionID {
     pub fn implement(self, actions_to_implement: ActionGroups, new_prototypes: CVec < Prototype >, world: &mut World) {
         world.send(self.as_raw(), MSG_Construction_implement(actions_to_implement, new_prototypes));
     }
+    
+    pub fn report_queue(self, reporter: CityReporterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Construction_report_queue(reporter));
+    }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
@@ -117,6 +121,8 @@ struct MSG_Construction_spawn(pub ConstructionID, );
 struct MSG_Construction_action_done(pub ConstructableID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Construction_implement(pub ActionGroups, pub CVec < Prototype >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Construction_report_queue(pub CityReporterID);
 
 impl Into<TemporalID> for ConstructionID {
     fn into(self) -> TemporalID {
@@ -146,4 +152,10 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.implement(actions_to_implement, new_prototypes, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Construction, _, _>(
+        |&MSG_Construction_report_queue(reporter), instance, world| {
+            instance.report_queue(reporter, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -1,10 +1,15 @@
 use kay::{World, Fate, ActorSystem};
 use compact::{CVec, CHashMap};
-use planning::{PrototypeID, Prototype, PrototypeKind, Action, ActionGroups};
+use descartes::{N, Area};
+use planning::{PrototypeID, Prototype, PrototypeKind, Action, ActionGroups, PlanResult};
 use time::{Temporal, TemporalID, Instant};
 use log::debug;
+use reporting::CityReporterID;
 const LOG_T: &str = "Construction";
 
+// tearing something down costs this fraction of what it cost to build it
+const DEMOLITION_COST_FRACTION: f32 = 0.1;
+
 pub trait Constructable {
     fn morph(&mut self, new_prototype: &Prototype, report_to: ConstructionID, world: &mut World);
     fn destruct(&mut self, report_to: ConstructionID, world: &mut World) -> Fate;
@@ -37,10 +42,54 @@ impl Prototype {
             _ => false,
         }
     }
+
+    /// What the city pays for building this prototype from scratch
+    pub fn construction_cost(&self) -> f32 {
+        match self.kind {
+            PrototypeKind::Road(ref road_prototype) => road_prototype.construction_cost(),
+            PrototypeKind::Lot(ref lot_prototype) => lot_prototype.construction_cost(),
+            PrototypeKind::Plant(ref plant_prototype) => plant_prototype.construction_cost(),
+        }
+    }
+}
+
+impl ActionGroups {
+    /// What the city pays for the actions that lead from `before` to `after`.
+    /// Morphing only costs as much as the new prototype is more expensive than the old one.
+    pub fn construction_cost(&self, before: &PlanResult, after: &PlanResult) -> f64 {
+        let cost_in = |result: &PlanResult, prototype_id: PrototypeID| {
+            result
+                .prototypes
+                .get(prototype_id)
+                .map_or(0.0, Prototype::construction_cost)
+        };
+
+        self.0
+            .iter()
+            .flat_map(|action_group| action_group.0.iter())
+            .map(|action| match *action {
+                Action::Construct(new_id) => cost_in(after, new_id),
+                Action::Morph(old_id, new_id) => {
+                    (cost_in(after, new_id) - cost_in(before, old_id)).max(0.0)
+                }
+                Action::Destruct(old_id) => DEMOLITION_COST_FRACTION * cost_in(before, old_id),
+            })
+            .map(f64::from)
+            .sum()
+    }
+}
+
+/// The size of an area in square meters
+pub fn surface(area: &Area) -> N {
+    area.primitives
+        .iter()
+        .map(|primitive| primitive.area())
+        .sum::<N>()
+        .abs()
 }
 
 // #[derive(Compact, Clone)]
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Construction {
     id: ConstructionID,
     constructed: CHashMap<PrototypeID, CVec<ConstructableID>>,
@@ -126,6 +175,10 @@ impl Construction {
                 .insert(new_prototype.id, new_prototype.clone());
         }
     }
+
+    pub fn report_queue(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.on_construction_queue(self.queued_action_groups.0.len() as u32, world);
+    }
 }
 
 impl Temporal for Construction {
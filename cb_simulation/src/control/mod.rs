@@ -0,0 +1,48 @@
+use kay::{World, TypedID};
+
+use time::TimeID;
+use planning::{PlanManagerID, ProjectID, GestureID, Gesture};
+use transport::lane::LaneID;
+use access::control_session;
+
+/// Commands for driving the simulation from outside of the actor system,
+/// for example from automation through the HTTP control API
+#[derive(Clone, Serialize, Deserialize)]
+pub enum ControlCommand {
+    SetSpeed(u16),
+    Pause,
+    Resume,
+    Step(u32),
+    SkipToNextMorning,
+    StartNewProject(ProjectID),
+    AddGesture(ProjectID, GestureID, Gesture),
+    Implement(ProjectID),
+    ManuallySpawnCarAddLane,
+}
+
+/// Sends the messages that carry out `command`, with the permissions of an admin
+pub fn apply(
+    command: ControlCommand,
+    time: TimeID,
+    plan_manager: PlanManagerID,
+    world: &mut World,
+) {
+    let session = control_session();
+    match command {
+        ControlCommand::SetSpeed(speed) => time.set_speed(speed, session, world),
+        ControlCommand::Pause => time.pause(session, world),
+        ControlCommand::Resume => time.resume(session, world),
+        ControlCommand::Step(n_ticks) => time.step(n_ticks, session, world),
+        ControlCommand::SkipToNextMorning => time.skip_to_next_morning(session, world),
+        ControlCommand::StartNewProject(project_id) => {
+            plan_manager.start_new_project(project_id, session, world)
+        }
+        ControlCommand::AddGesture(project_id, gesture_id, gesture) => {
+            plan_manager.add_gesture(project_id, gesture_id, gesture, session, world)
+        }
+        ControlCommand::Implement(project_id) => plan_manager.implement(project_id, session, world),
+        ControlCommand::ManuallySpawnCarAddLane => {
+            LaneID::global_broadcast(world).manually_spawn_car_add_lane(world)
+        }
+    }
+}
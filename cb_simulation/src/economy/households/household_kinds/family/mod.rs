@@ -1,7 +1,7 @@
 use kay::{ActorSystem, World, Actor};
 use util::random::{seed, Rng};
 
-use time::{TimeOfDay, TimeOfDayRange, Instant, Duration, Ticks, TimeID, Temporal,
+use time::{TimeOfDay, TimeOfDayRange, Schedule, Instant, Duration, Ticks, TimeID, Temporal,
 TemporalID};
 use economy::resources::Resource;
 use economy::resources::Resource::*;
@@ -16,7 +16,7 @@ use self::names::{family_name, member_name};
 use economy::households::{Household, HouseholdID, HouseholdCore,
 MemberIdx, Offer, OfferID, OfferIdx};
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Family {
     id: FamilyID,
     home: BuildingID,
@@ -40,7 +40,7 @@ impl Family {
             home.into(),
             vec![Offer::new(
                 MemberIdx(0),
-                TimeOfDayRange::new(16, 0, 11, 0),
+                Schedule::daily(TimeOfDayRange::new(16, 0, 11, 0)),
                 Deal::new(Some((Wakefulness, 3.0)), Duration::from_hours(1)),
                 1,
                 true,
@@ -220,8 +220,8 @@ impl Household for Family {
 }
 
 impl Temporal for Family {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        self.on_tick(current_instant, world);
+    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        self.on_tick(dt, current_instant, world);
     }
 }
 
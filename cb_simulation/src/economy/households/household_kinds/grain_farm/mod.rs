@@ -1,5 +1,5 @@
-use kay::{ActorSystem, World, TypedID, Actor};
-use time::{TimeOfDay, TimeOfDayRange, Duration, TimeID, Ticks};
+use kay::{ActorSystem, World, Actor};
+use time::{TimeOfDay, TimeOfDayRange, Schedule, Weekdays, Seasons, Season, Duration, TimeID, Ticks};
 use economy::resources::Resource;
 use economy::resources::Resource::*;
 use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
@@ -7,7 +7,7 @@ use land_use::buildings::BuildingID;
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct GrainFarm {
     id: GrainFarmID,
     site: BuildingID,
@@ -34,7 +34,7 @@ impl GrainFarm {
                 vec![
                     Offer::new(
                         MemberIdx(0),
-                        TimeOfDayRange::new(7, 0, 20, 0),
+                        Schedule::daily(TimeOfDayRange::new(7, 0, 20, 0)),
                         Deal::new(
                             vec![(Resource::Grain, 200.0), (Resource::Money, -200.0 * 0.13)],
                             Duration::from_minutes(10),
@@ -44,7 +44,9 @@ impl GrainFarm {
                     ),
                     Offer::new(
                         MemberIdx(0),
-                        TimeOfDayRange::new(5, 0, 15, 0),
+                        Schedule::daily(TimeOfDayRange::new(5, 0, 15, 0))
+                            .on(Weekdays::WORKDAYS)
+                            .during(Seasons::except(Season::Winter)),
                         Deal::new(Some((Resource::Money, 40.0)), Duration::from_hours(4)),
                         2,
                         false,
@@ -105,17 +107,10 @@ impl Household for GrainFarm {
     }
 }
 
-use time::{Temporal, TemporalID, Sleeper, SleeperID, Instant, TICKS_PER_SIM_SECOND};
-const UPDATE_EVERY_N_SECS: u32 = 4;
-
+use time::{Temporal, TemporalID, Sleeper, SleeperID, Instant};
 impl Temporal for GrainFarm {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
-        }
+    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        self.on_tick(dt, current_instant, world);
     }
 }
 
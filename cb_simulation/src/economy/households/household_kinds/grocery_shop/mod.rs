@@ -1,5 +1,5 @@
-use kay::{ActorSystem, World, TypedID, Actor};
-use time::{TimeOfDay, TimeOfDayRange, Duration, TimeID, Ticks};
+use kay::{ActorSystem, World, Actor};
+use time::{TimeOfDay, TimeOfDayRange, Schedule, Weekdays, Duration, TimeID, Ticks};
 use economy::resources::Resource;
 use economy::resources::Resource::*;
 use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
@@ -7,7 +7,7 @@ use land_use::buildings::BuildingID;
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct GroceryShop {
     id: GroceryShopID,
     site: BuildingID,
@@ -34,7 +34,7 @@ impl GroceryShop {
                 vec![
                     Offer::new(
                         MemberIdx(0),
-                        TimeOfDayRange::new(7, 0, 20, 0),
+                        Schedule::daily(TimeOfDayRange::new(7, 0, 20, 0)),
                         Deal::new(
                             vec![(Groceries, 30.0), (Money, -30.0 * 2.7)],
                             Duration::from_minutes(30),
@@ -44,7 +44,8 @@ impl GroceryShop {
                     ),
                     Offer::new(
                         MemberIdx(0),
-                        TimeOfDayRange::new(7, 0, 15, 0),
+                        Schedule::daily(TimeOfDayRange::new(7, 0, 15, 0))
+                            .on(Weekdays::WORKDAYS),
                         Deal::new(Some((Money, 50.0)), Duration::from_hours(5)),
                         5,
                         false,
@@ -169,17 +170,10 @@ impl EvaluationRequester for GroceryShop {
     }
 }
 
-use time::{Temporal, TemporalID, Sleeper, SleeperID, Instant, TICKS_PER_SIM_SECOND};
-const UPDATE_EVERY_N_SECS: u32 = 4;
-
+use time::{Temporal, TemporalID, Sleeper, SleeperID, Instant};
 impl Temporal for GroceryShop {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
-        }
+    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        self.on_tick(dt, current_instant, world);
     }
 }
 
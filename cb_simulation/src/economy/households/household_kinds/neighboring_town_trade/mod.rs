@@ -1,5 +1,5 @@
 use kay::{ActorSystem, World, Actor};
-use time::{TimeOfDay, TimeOfDayRange, Duration, Instant, Temporal, TemporalID,
+use time::{TimeOfDay, TimeOfDayRange, Schedule, Weekdays, Duration, Instant, Temporal, TemporalID,
 TimeID, Ticks};
 use economy::resources::Resource;
 use economy::resources::Resource::*;
@@ -10,7 +10,7 @@ use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripRes
 
 use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct NeighboringTownTrade {
     id: NeighboringTownTradeID,
     town: BuildingID,
@@ -29,7 +29,8 @@ impl NeighboringTownTrade {
         let offers = vec![
             Offer::new(
                 MemberIdx(0),
-                TimeOfDayRange::new(5, 0, 15, 0),
+                Schedule::daily(TimeOfDayRange::new(5, 0, 15, 0))
+                    .on(Weekdays::WORKDAYS),
                 Deal::new(Some((Resource::Money, 50.0)), Duration::from_hours(5)),
                 300,
                 false,
@@ -56,7 +57,7 @@ impl NeighboringTownTrade {
             // ),
             Offer::new(
                 MemberIdx(0),
-                TimeOfDayRange::new(7, 0, 20, 0),
+                Schedule::daily(TimeOfDayRange::new(7, 0, 20, 0)),
                 Deal::new(
                     vec![(Groceries, 30.0), (Money, -30.0 * 2.7)],
                     Duration::from_minutes(30),
@@ -66,7 +67,7 @@ impl NeighboringTownTrade {
             ),
             Offer::new(
                 MemberIdx(0),
-                TimeOfDayRange::new(7, 0, 20, 0),
+                Schedule::daily(TimeOfDayRange::new(7, 0, 20, 0)),
                 Deal::new(
                     vec![(Resource::Produce, 20.0), (Resource::Money, -20.0 * 1.3)],
                     Duration::from_minutes(10),
@@ -76,7 +77,7 @@ impl NeighboringTownTrade {
             ),
             Offer::new(
                 MemberIdx(0),
-                TimeOfDayRange::new(7, 0, 20, 0),
+                Schedule::daily(TimeOfDayRange::new(7, 0, 20, 0)),
                 Deal::new(
                     vec![(Resource::Grain, 200.0), (Resource::Money, -200.0 * 0.13)],
                     Duration::from_minutes(10),
@@ -86,7 +87,7 @@ impl NeighboringTownTrade {
             ),
             Offer::new(
                 MemberIdx(0),
-                TimeOfDayRange::new(7, 0, 20, 0),
+                Schedule::daily(TimeOfDayRange::new(7, 0, 20, 0)),
                 Deal::new(
                     vec![(Resource::Flour, 200.0), (Resource::Money, -200.0 * 0.3)],
                     Duration::from_minutes(10),
@@ -96,7 +97,7 @@ impl NeighboringTownTrade {
             ),
             Offer::new(
                 MemberIdx(0),
-                TimeOfDayRange::new(7, 0, 20, 0),
+                Schedule::daily(TimeOfDayRange::new(7, 0, 20, 0)),
                 Deal::new(
                     vec![
                         (Resource::BakedGoods, 100.0),
@@ -109,7 +110,7 @@ impl NeighboringTownTrade {
             ),
             Offer::new(
                 MemberIdx(0),
-                TimeOfDayRange::new(7, 0, 20, 0),
+                Schedule::daily(TimeOfDayRange::new(7, 0, 20, 0)),
                 Deal::new(
                     vec![(Resource::Meat, 5.0), (Resource::Money, -5.0 * 3.0)],
                     Duration::from_minutes(10),
@@ -119,7 +120,7 @@ impl NeighboringTownTrade {
             ),
             Offer::new(
                 MemberIdx(0),
-                TimeOfDayRange::new(7, 0, 20, 0),
+                Schedule::daily(TimeOfDayRange::new(7, 0, 20, 0)),
                 Deal::new(
                     vec![
                         (Resource::DairyGoods, 10.0),
@@ -291,8 +292,8 @@ impl TripListener for NeighboringTownTrade {
 }
 
 impl Temporal for NeighboringTownTrade {
-    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        self.on_tick(current_instant, world);
+    fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        self.on_tick(dt, current_instant, world);
     }
 }
 
@@ -103,8 +103,8 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_stop_task(member, location));
     }
     
-    pub fn on_tick(self, current_instant: Instant, world: &mut World) {
-        world.send(self.as_raw(), MSG_Household_on_tick(current_instant));
+    pub fn on_tick(self, dt: f32, current_instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_on_tick(dt, current_instant));
     }
     
     pub fn evaluate(self, offer_idx: OfferIdx, instant: Instant, location: RoughLocationID, requester: EvaluationRequesterID, world: &mut World) {
@@ -286,8 +286,8 @@ impl HouseholdID {
         );
         
         system.add_handler::<A, _, _>(
-            |&MSG_Household_on_tick(current_instant), instance, world| {
-                instance.on_tick(current_instant, world); Fate::Live
+            |&MSG_Household_on_tick(dt, current_instant), instance, world| {
+                instance.on_tick(dt, current_instant, world); Fate::Live
             }, false
         );
         
@@ -384,7 +384,7 @@ struct MSG_Household_start_task(pub MemberIdx, pub Instant, pub RoughLocationID)
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_stop_task(pub MemberIdx, pub Option < RoughLocationID >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Household_on_tick(pub Instant);
+struct MSG_Household_on_tick(pub f32, pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_evaluate(pub OfferIdx, pub Instant, pub RoughLocationID, pub EvaluationRequesterID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
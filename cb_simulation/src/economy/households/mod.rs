@@ -1,11 +1,13 @@
 use kay::{ActorSystem, World, Actor, TypedID, Fate};
 use compact::{CVec, CDict, COption};
 use time::{Duration, TimeOfDay, Instant, Ticks, TimeID, TICKS_PER_SIM_SECOND, Sleeper,
-Temporal};
+SleeperID, ScheduleID, Temporal, Date};
 use util::async_counter::AsyncCounter;
 use util::random::{seed, Rng};
 use ordered_float::OrderedFloat;
 use log::{debug, info, warn};
+use tuning::decision_pause;
+use economy::treasury::{TreasuryID, TaxedDeal, levy_taxes};
 const LOG_T: &str = "Households";
 
 pub mod tasks;
@@ -37,7 +39,6 @@ use self::tasks::{Task, TaskState, TaskEndSchedulerID};
 pub use self::offers::{Offer, OfferIdx, OfferID};
 
 const N_TOP_PROBLEMS: usize = 5;
-const DECISION_PAUSE: Ticks = Ticks(200);
 const UPDATE_EVERY_N_SECS: u32 = 4;
 
 // TODO: make kay_codegen figure this out on it's own
@@ -183,6 +184,7 @@ pub trait Household:
 
     fn destroy(&mut self, world: &mut World) {
         self.core_mut().being_destroyed = true;
+        TimeID::local_first(world).cancel_wake_up(self.core().decision_schedule, world);
 
         for &Entry(_, offer) in self.core().used_offers.iter() {
             offer
@@ -274,9 +276,7 @@ pub trait Household:
         let time = TimeOfDay::from(instant);
         let top_problems = self.top_problems(member, time);
 
-        if top_problems.is_empty() {
-            TimeID::local_first(world).wake_up_in(DECISION_PAUSE, self.id_as(), world);
-        } else {
+        if !top_problems.is_empty() {
             let mut decision_entries = CDict::<Resource, DecisionResourceEntry>::new();
             let id_as_eval_requester = self.id_as();
             let log_as = self.id();
@@ -365,8 +365,8 @@ pub trait Household:
                                     format!(
                                         "Got eval'd deal for {}, {:?} -> {:?}\n",
                                         evaluated_deal.deal.main_given(),
-                                        evaluated_deal.opening_hours.start.hours_minutes(),
-                                        evaluated_deal.opening_hours.end.hours_minutes(),
+                                        evaluated_deal.opening_hours.hours.start.hours_minutes(),
+                                        evaluated_deal.opening_hours.hours.end.hours_minutes(),
                                     ),
                                     log_as,
                                     world,
@@ -458,7 +458,6 @@ pub trait Household:
     fn choose_deal(&mut self, world: &mut World) {
         let log_as = self.id();
         let id_as_household = self.id_as();
-        let id_as_sleeper = self.id_as();
         debug(LOG_T, "Choosing deal!", self.id(), world);
 
         let maybe_best_info = {
@@ -541,7 +540,6 @@ pub trait Household:
                 world,
             );
             self.core_mut().decision_state = DecisionState::None;
-            TimeID::local_first(world).wake_up_in(DECISION_PAUSE, id_as_sleeper, world);
         }
 
         fn most_useful_evaluated_deal(
@@ -577,7 +575,6 @@ pub trait Household:
         self.core_mut().decision_state =
             if let DecisionState::WaitingForTrip(member) = self.core().decision_state {
                 self.core_mut().member_tasks[member.as_idx()].state = TaskState::InTrip(trip);
-                TimeID::local_first(world).wake_up_in(DECISION_PAUSE, self.id_as(), world);
                 DecisionState::None
             } else {
                 panic!("Should be in waiting for trip state")
@@ -742,12 +739,19 @@ pub trait Household:
         }
     }
 
-    fn on_tick(&mut self, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id().as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+    fn on_tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
+        // time warps tick less often with a longer dt, so this counts all updates passed since
+        let update_every_n_ticks = (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize;
+        let elapsed_ticks = (dt * TICKS_PER_SIM_SECOND as f32).round() as usize;
+        let phased_ticks = current_instant.ticks() + self.id().as_raw().instance_id as usize;
+        let n_updates = phased_ticks / update_every_n_ticks
+            - phased_ticks.saturating_sub(elapsed_ticks) / update_every_n_ticks;
+
+        if n_updates > 0 {
+            self.decay(
+                Duration(n_updates as u32 * UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND),
+                world,
+            );
         }
     }
 
@@ -769,9 +773,11 @@ pub trait Household:
     ) {
         let offer = self.get_offer(offer_idx);
 
-        if offer
-            .opening_hours
-            .end_after_on_same_day(TimeOfDay::from(instant))
+        if offer.opening_hours.is_open_on(Date::from(instant))
+            && offer
+                .opening_hours
+                .hours
+                .end_after_on_same_day(TimeOfDay::from(instant))
         {
             let search_result = EvaluatedSearchResult {
                 resource: offer.deal.main_given(),
@@ -818,8 +824,16 @@ pub trait Household:
         world: &mut World,
     ) {
         let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
-        self.provide_deal(&offer.deal, offer.offering_member, world);
-        requester.receive_deal(offer.deal, requester_member, world);
+        let TaxedDeal {
+            provider_deal,
+            requester_deal,
+            tax,
+        } = levy_taxes(&offer.deal);
+        self.provide_deal(&provider_deal, offer.offering_member, world);
+        requester.receive_deal(requester_deal, requester_member, world);
+        if let Some((kind, amount)) = tax {
+            TreasuryID::global_first(world).collect_tax(kind, f64::from(amount), world);
+        }
     }
 
     fn request_receive_undo_deal(
@@ -830,8 +844,16 @@ pub trait Household:
         world: &mut World,
     ) {
         let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
-        self.receive_deal(&offer.deal, offer.offering_member, world);
-        requester.provide_deal(offer.deal, requester_member, world);
+        let TaxedDeal {
+            provider_deal,
+            requester_deal,
+            tax,
+        } = levy_taxes(&offer.deal);
+        self.receive_deal(&provider_deal, offer.offering_member, world);
+        requester.provide_deal(requester_deal, requester_member, world);
+        if let Some((kind, amount)) = tax {
+            TreasuryID::global_first(world).collect_tax(kind, -f64::from(amount), world);
+        }
     }
 
     fn started_using(
@@ -985,14 +1007,14 @@ pub enum ResultAspect {
     SetTarget(u32),
 }
 
-#[derive(Compact, Clone, Serialize)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct DecisionResourceEntry {
     results_counter: AsyncCounter,
     best_deal: COption<EvaluatedDeal>,
     best_deal_usefulness: f32,
 }
 
-#[derive(Compact, Clone, Serialize)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub enum DecisionState {
     None,
     Choosing(
@@ -1004,7 +1026,7 @@ pub enum DecisionState {
     WaitingForTrip(MemberIdx),
 }
 
-#[derive(Compact, Clone, Serialize)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct HouseholdCore {
     pub resources: Inventory,
     pub member_resources: CVec<Inventory>,
@@ -1014,6 +1036,8 @@ pub struct HouseholdCore {
     pub member_used_offers: CVec<ResourceMap<OfferID>>,
     pub provided_offers: CVec<Offer>,
     pub being_destroyed: bool,
+    // wakes the household up regularly to decide what its idle members do next
+    pub decision_schedule: ScheduleID,
 }
 
 impl HouseholdCore {
@@ -1037,6 +1061,14 @@ impl HouseholdCore {
             )
         }
 
+        let decision_schedule = ScheduleID::new();
+        TimeID::local_first(world).wake_up_every(
+            Duration(decision_pause().0 / TICKS_PER_SIM_SECOND),
+            SleeperID::from_raw(owner.as_raw()),
+            decision_schedule,
+            world,
+        );
+
         HouseholdCore {
             resources: Inventory::new(),
             member_resources: vec![Inventory::new(); n_members].into(),
@@ -1046,6 +1078,7 @@ impl HouseholdCore {
             member_used_offers: vec![ResourceMap::new(); n_members].into(),
             provided_offers,
             being_destroyed: false,
+            decision_schedule,
         }
     }
 }
@@ -1,7 +1,7 @@
 use compact::CVec;
 use economy::market::Deal;
 use super::{HouseholdID, MemberIdx};
-use time::TimeOfDayRange;
+use time::Schedule;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct OfferIdx(pub u16);
@@ -12,10 +12,10 @@ pub struct OfferID {
     pub idx: OfferIdx,
 }
 
-#[derive(Compact, Clone, Serialize)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Offer {
     pub offering_member: MemberIdx,
-    pub opening_hours: TimeOfDayRange,
+    pub opening_hours: Schedule,
     pub deal: Deal,
     pub max_users: u32,
     pub is_internal: bool,
@@ -27,7 +27,7 @@ pub struct Offer {
 impl Offer {
     pub fn new(
         offering_member: MemberIdx,
-        opening_hours: TimeOfDayRange,
+        opening_hours: Schedule,
         deal: Deal,
         max_users: usize,
         is_internal: bool,
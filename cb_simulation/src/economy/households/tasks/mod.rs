@@ -33,7 +33,7 @@ impl Task {
     }
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct TaskEndScheduler {
     id: TaskEndSchedulerID,
     task_ends: CVec<(Instant, HouseholdID, MemberIdx)>,
@@ -1,9 +1,11 @@
 use kay::{World, ActorSystem, TypedID};
 use compact::COption;
 use land_use::buildings::{UnitType, BuildingID, UnitIdx};
-use time::{Sleeper, SleeperID, Instant, TimeID, Duration};
+use time::{Sleeper, SleeperID, Instant, TimeID};
+use tuning::immigration_pace;
 use util::random::{seed, Rng};
 use log::{debug};
+use access::server_session;
 const LOG_T: &str = "Immigration/Development";
 
 use economy::households::household_kinds;
@@ -23,7 +25,7 @@ use planning::{PlanManagerID, Project, PrototypeID, Plan, GestureID, Gesture, Ge
 // TODO: somehow get rid of this horrible duplication by having something like
 // a pointer to an abstract Household trait...
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum HouseholdTypeToSpawn {
     Family,
     GroceryShop,
@@ -61,7 +63,7 @@ pub fn building_style_for(household_type: HouseholdTypeToSpawn) -> BuildingStyle
     }
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct ImmigrationManager {
     id: ImmigrationManagerID,
     time: TimeID,
@@ -76,7 +78,7 @@ impl ImmigrationManager {
         development_manager: DevelopmentManagerID,
         world: &mut World,
     ) -> ImmigrationManager {
-        time.wake_up_in(IMMIGRATION_PACE.into(), id.into(), world);
+        time.wake_up_in(immigration_pace().into(), id.into(), world);
 
         ImmigrationManager {
             id,
@@ -87,14 +89,12 @@ impl ImmigrationManager {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum ImmigrationManagerState {
     Idle,
     FindingBuilding(HouseholdTypeToSpawn),
 }
 
-const IMMIGRATION_PACE: Duration = Duration(10);
-
 impl Sleeper for ImmigrationManager {
     fn wake(&mut self, current_instant: Instant, world: &mut World) {
         self.state = match self.state {
@@ -173,7 +173,7 @@ impl Sleeper for ImmigrationManager {
         };
 
         self.time
-            .wake_up_in(IMMIGRATION_PACE.into(), self.id.into(), world);
+            .wake_up_in(immigration_pace().into(), self.id.into(), world);
     }
 }
 
@@ -220,7 +220,7 @@ impl ImmigrationManager {
     }
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct DevelopmentManager {
     id: DevelopmentManagerID,
     time: TimeID,
@@ -254,7 +254,7 @@ impl DevelopmentManager {
             self.building_to_develop = COption(Some(building_style));
             VacantLotID::global_broadcast(world).suggest_lot(building_style, self.id, world);
             self.time
-                .wake_up_in(IMMIGRATION_PACE.into(), self.id.into(), world);
+                .wake_up_in(immigration_pace().into(), self.id.into(), world);
         }
     }
 
@@ -281,6 +281,7 @@ impl DevelopmentManager {
                         ),
                     )))),
                     vec![based_on].into(),
+                    server_session(),
                     world,
                 );
                 self.building_to_develop = COption(None);
@@ -113,6 +113,10 @@ impl MarketID {
     pub fn withdraw(self, resource: Resource, offer: OfferID, world: &mut World) {
         world.send(self.as_raw(), MSG_Market_withdraw(resource, offer));
     }
+    
+    pub fn report_offers(self, reporter: CityReporterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_report_offers(reporter));
+    }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
@@ -123,6 +127,8 @@ struct MSG_Market_search(pub Instant, pub RoughLocationID, pub Resource, pub Eva
 struct MSG_Market_register(pub Resource, pub OfferID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Market_withdraw(pub Resource, pub OfferID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Market_report_offers(pub CityReporterID);
 
 
 impl Actor for TripCostEstimator {
@@ -211,6 +217,12 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.withdraw(resource, offer, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Market, _, _>(
+        |&MSG_Market_report_offers(reporter), instance, world| {
+            instance.report_offers(reporter, world); Fate::Live
+        }, false
+    );
     LocationRequesterID::register_implementor::<TripCostEstimator>(system);
     DistanceRequesterID::register_implementor::<TripCostEstimator>(system);
     system.add_spawner::<TripCostEstimator, _, _>(
@@ -2,9 +2,10 @@ use kay::{ActorSystem, Fate, World, Actor};
 use compact::{CVec, CDict};
 use super::resources::{Inventory, Entry, Resource, ResourceAmount};
 use super::households::OfferID;
-use time::{TimeOfDayRange, Duration, Instant};
+use time::{Schedule, Duration, Instant};
 use transport::pathfinding::{RoughLocationID, LocationRequesterID};
 use log::warn;
+use reporting::CityReporterID;
 const LOG_T: &str = "Market";
 
 #[derive(Compact, Clone, Serialize, Deserialize)]
@@ -38,7 +39,7 @@ pub trait EvaluationRequester {
     fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World);
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Market {
     id: MarketID,
     offers_by_resource: CDict<Resource, CVec<OfferID>>,
@@ -85,16 +86,25 @@ impl Market {
         }
         offer.household.withdrawal_confirmed(offer.idx, world);
     }
+
+    pub fn report_offers(&mut self, reporter: CityReporterID, world: &mut World) {
+        let offers = self
+            .offers_by_resource
+            .pairs()
+            .map(|(resource, offers)| (*resource, offers.len() as u32))
+            .collect();
+        reporter.on_market_offers(offers, world);
+    }
 }
 
 #[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct EvaluatedDeal {
     pub offer: OfferID,
     pub deal: Deal,
-    pub opening_hours: TimeOfDayRange,
+    pub opening_hours: Schedule,
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct EvaluatedSearchResult {
     pub resource: Resource,
     pub evaluated_deals: CVec<EvaluatedDeal>,
@@ -103,7 +113,7 @@ pub struct EvaluatedSearchResult {
 use transport::pathfinding::{PreciseLocation, LocationRequester, DistanceRequester,
 DistanceRequesterID};
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct TripCostEstimator {
     id: TripCostEstimatorID,
     requester: EvaluationRequesterID,
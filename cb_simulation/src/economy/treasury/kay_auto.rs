@@ -0,0 +1,127 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for Treasury {
+    type ID = TreasuryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct TreasuryID {
+    _raw_id: RawID
+}
+
+impl TypedID for TreasuryID {
+    type Target = Treasury;
+
+    fn from_raw(id: RawID) -> Self {
+        TreasuryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TreasuryID {
+    pub fn spawn(time: TimeID, plan_manager: PlanManagerID, world: &mut World) -> Self {
+        let id = TreasuryID::from_raw(world.allocate_instance_id::<Treasury>());
+        let swarm = world.local_broadcast::<Treasury>();
+        world.send(swarm, MSG_Treasury_spawn(id, time, plan_manager));
+        id
+    }
+    
+    pub fn collect_tax(self, kind: TaxKind, amount: f64, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_collect_tax(kind, amount));
+    }
+    
+    pub fn pay_for_construction(self, project_id: ProjectID, cost: f64, plan_manager: PlanManagerID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_pay_for_construction(project_id, cost, plan_manager));
+    }
+    
+    pub fn refund_construction(self, project_id: ProjectID, cost: f64, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_refund_construction(project_id, cost));
+    }
+    
+    pub fn pay_for_maintenance(self, infrastructure_value: f64, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_pay_for_maintenance(infrastructure_value));
+    }
+    
+    pub fn report_finances(self, reporter: CityReporterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Treasury_report_finances(reporter));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_spawn(pub TreasuryID, pub TimeID, pub PlanManagerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_collect_tax(pub TaxKind, pub f64);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_pay_for_construction(pub ProjectID, pub f64, pub PlanManagerID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_refund_construction(pub ProjectID, pub f64);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_pay_for_maintenance(pub f64);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_report_finances(pub CityReporterID);
+
+impl Into<SleeperID> for TreasuryID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    SleeperID::register_implementor::<Treasury>(system);
+    system.add_spawner::<Treasury, _, _>(
+        |&MSG_Treasury_spawn(id, time, plan_manager), world| {
+            Treasury::spawn(id, time, plan_manager, world)
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_collect_tax(kind, amount), instance, world| {
+            instance.collect_tax(kind, amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_pay_for_construction(project_id, cost, plan_manager), instance, world| {
+            instance.pay_for_construction(project_id, cost, plan_manager, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_refund_construction(project_id, cost), instance, world| {
+            instance.refund_construction(project_id, cost, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_pay_for_maintenance(infrastructure_value), instance, world| {
+            instance.pay_for_maintenance(infrastructure_value, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Treasury, _, _>(
+        |&MSG_Treasury_report_finances(reporter), instance, world| {
+            instance.report_finances(reporter, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
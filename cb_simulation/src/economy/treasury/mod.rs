@@ -0,0 +1,229 @@
+use kay::{ActorSystem, World};
+use compact::CVec;
+use time::{TimeID, Instant, Duration, Date, Sleeper, SleeperID, ScheduleID};
+use planning::{PlanManagerID, ProjectID};
+use economy::market::Deal;
+use economy::resources::Resource;
+use reporting::CityReporterID;
+use tuning::{starting_city_funds, income_tax_rate, sales_tax_rate};
+use log::{info, warn};
+const LOG_T: &str = "Treasury";
+
+// how many days of income and spending the ledger remembers
+const N_LEDGER_PERIODS: usize = 100;
+
+// the share of the construction cost of all infrastructure that is spent each day to keep it up
+const DAILY_MAINTENANCE_FRACTION: f64 = 0.001;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum TaxKind {
+    /// Withheld from the money households earn by working
+    Income,
+    /// Withheld from the money households get for selling things
+    Sales,
+}
+
+/// What the city earned and spent during one day.
+/// City budgets are large sums of many small amounts, which would get lost in `f32`s.
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct LedgerPeriod {
+    pub day: u32,
+    pub income_taxes: f64,
+    pub sales_taxes: f64,
+    pub construction: f64,
+    pub maintenance: f64,
+}
+
+impl LedgerPeriod {
+    pub fn net(&self) -> f64 {
+        self.income_taxes + self.sales_taxes - self.construction - self.maintenance
+    }
+}
+
+/// The money of the city government, which it gets from taxes
+/// and uses to pay for construction and maintenance
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct Treasury {
+    id: TreasuryID,
+    plan_manager: PlanManagerID,
+    balance: f64,
+    ledger: CVec<LedgerPeriod>,
+}
+
+impl Treasury {
+    pub fn spawn(
+        id: TreasuryID,
+        time: TimeID,
+        plan_manager: PlanManagerID,
+        world: &mut World,
+    ) -> Treasury {
+        time.wake_up_every(Duration::from_hours(24), id.into(), ScheduleID::new(), world);
+
+        Treasury {
+            id,
+            plan_manager,
+            balance: f64::from(starting_city_funds()),
+            ledger: CVec::new(),
+        }
+    }
+
+    fn current_period(&mut self) -> &mut LedgerPeriod {
+        let day = Date::from(::time::latest_instant()).day() as u32;
+
+        if self.ledger.last().map_or(true, |period| period.day != day) {
+            if self.ledger.len() >= N_LEDGER_PERIODS {
+                self.ledger.remove(0);
+            }
+            self.ledger.push(LedgerPeriod {
+                day,
+                ..Default::default()
+            });
+        }
+
+        self.ledger
+            .last_mut()
+            .expect("should have just ensured a current period")
+    }
+
+    /// Receives a tax withheld from a deal between households,
+    /// negative amounts refund taxes of deals that were undone
+    pub fn collect_tax(&mut self, kind: TaxKind, amount: f64, _: &mut World) {
+        self.balance += amount;
+        let period = self.current_period();
+        match kind {
+            TaxKind::Income => period.income_taxes += amount,
+            TaxKind::Sales => period.sales_taxes += amount,
+        }
+    }
+
+    /// Pays for implementing a project if the city can afford it.
+    /// `plan_manager` only implements the project once it was paid for.
+    pub fn pay_for_construction(
+        &mut self,
+        project_id: ProjectID,
+        cost: f64,
+        plan_manager: PlanManagerID,
+        world: &mut World,
+    ) {
+        let paid = cost <= self.balance;
+
+        if paid {
+            self.balance -= cost;
+            self.current_period().construction += cost;
+            info(
+                LOG_T,
+                format!(
+                    "Paid {:.0} for project {:?}, {:.0} left",
+                    cost, project_id, self.balance
+                ),
+                self.id,
+                world,
+            );
+        } else {
+            warn(
+                LOG_T,
+                format!(
+                    "Can't afford project {:?}, it costs {:.0} but only {:.0} are left",
+                    project_id, cost, self.balance
+                ),
+                self.id,
+                world,
+            );
+        }
+
+        plan_manager.on_construction_paid(project_id, paid, world);
+    }
+
+    /// Gives back what was paid for a project that couldn't be implemented after all
+    pub fn refund_construction(&mut self, project_id: ProjectID, cost: f64, world: &mut World) {
+        self.balance += cost;
+        self.current_period().construction -= cost;
+        info(
+            LOG_T,
+            format!(
+                "Got back {:.0} for project {:?} that couldn't be implemented",
+                cost, project_id
+            ),
+            self.id,
+            world,
+        );
+    }
+
+    /// Pays for a day of maintenance of infrastructure with the given construction cost.
+    /// Existing infrastructure can't be left alone, so this goes into debt if necessary.
+    pub fn pay_for_maintenance(&mut self, infrastructure_value: f64, world: &mut World) {
+        let cost = infrastructure_value * DAILY_MAINTENANCE_FRACTION;
+        let was_in_debt = self.balance < 0.0;
+        self.balance -= cost;
+        self.current_period().maintenance += cost;
+
+        if self.balance < 0.0 && !was_in_debt {
+            warn(
+                LOG_T,
+                format!(
+                    "Maintenance of {:.0} put the city into debt of {:.0}",
+                    cost, -self.balance
+                ),
+                self.id,
+                world,
+            );
+        }
+    }
+
+    pub fn report_finances(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.on_finances(self.balance, self.ledger.clone(), world);
+    }
+}
+
+impl Sleeper for Treasury {
+    fn wake(&mut self, _current_instant: Instant, world: &mut World) {
+        self.plan_manager.report_infrastructure_value(self.id, world);
+    }
+}
+
+/// A deal as it is actually carried out, after taxes were withheld
+pub struct TaxedDeal {
+    /// What the offering household provides
+    pub provider_deal: Deal,
+    /// What the requesting household receives
+    pub requester_deal: Deal,
+    pub tax: Option<(TaxKind, f32)>,
+}
+
+/// Withholds taxes from the money that changes hands in `deal`. Money flowing to the
+/// requester is a wage and pays income tax, money flowing to the provider pays sales tax.
+pub fn levy_taxes(deal: &Deal) -> TaxedDeal {
+    let money = deal.delta.get(Resource::Money).cloned().unwrap_or(0.0);
+    let mut provider_deal = deal.clone();
+    let mut requester_deal = deal.clone();
+
+    let tax = if money > 0.0 {
+        let tax = money * income_tax_rate();
+        requester_deal.delta.insert(Resource::Money, money - tax);
+        Some((TaxKind::Income, tax))
+    } else if money < 0.0 {
+        let tax = -money * sales_tax_rate();
+        provider_deal.delta.insert(Resource::Money, money + tax);
+        Some((TaxKind::Sales, tax))
+    } else {
+        None
+    };
+
+    TaxedDeal {
+        provider_deal,
+        requester_deal,
+        tax: tax.filter(|&(_, amount)| amount > 0.0),
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Treasury>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID, plan_manager: PlanManagerID) -> TreasuryID {
+    TreasuryID::spawn(time, plan_manager, world)
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -9,6 +9,7 @@ use land_use::zone_planning::{LotPrototype, LotOccupancy};
 use land_use::buildings::BuildingStyle;
 use land_use::buildings::architecture::footprint_area;
 use util::random::{seed, Rng};
+use access::server_session;
 use noise::{NoiseFn, BasicMulti, Seedable, MultiFractal};
 
 pub mod ui;
@@ -54,6 +55,16 @@ impl PlantPrototype {
         other_plant_proto.position.rough_eq_by(self.position, 0.5)
             && other_plant_proto.vegetation_type == self.vegetation_type
     }
+
+    pub fn construction_cost(&self) -> f32 {
+        match self.vegetation_type {
+            VegetationType::Shrub => 20.0,
+            VegetationType::Bush => 40.0,
+            VegetationType::SmallTree => 100.0,
+            VegetationType::MediumTree => 200.0,
+            VegetationType::LargeTree => 400.0,
+        }
+    }
 }
 
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
@@ -62,7 +73,7 @@ pub enum PlantIntent {
     NaturalGrowth,
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Plant {
     id: PlantID,
     proto: PlantPrototype,
@@ -256,7 +267,7 @@ pub fn spawn(world: &mut World, plan_manager: PlanManagerID) {
     ));
     let project = Project::from_plan(Plan::from_gestures(gestures));
 
-    plan_manager.implement_artificial_project(project, CVec::new(), world);
+    plan_manager.implement_artificial_project(project, CVec::new(), server_session(), world);
 }
 
 mod kay_auto;
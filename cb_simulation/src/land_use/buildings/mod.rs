@@ -19,10 +19,10 @@ use super::ui::{LandUseUIID};
 use log::debug;
 const LOG_T: &str = "Buildings";
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Unit(Option<HouseholdID>, UnitType);
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum UnitType {
     Dwelling,
     Retail,
@@ -58,7 +58,7 @@ impl BuildingStyle {
     }
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Building {
     id: BuildingID,
     units: CVec<Unit>,
@@ -4,10 +4,17 @@ use descartes::PointContainer;
 use land_use::zone_planning::{LotPrototype, LotOccupancy};
 use land_use::vacant_lots::VacantLotID;
 use land_use::buildings::BuildingID;
-use construction::{ConstructionID, ConstructableID};
+use construction::{ConstructionID, ConstructableID, surface};
 use planning::PrototypeID;
 
+// roads are paid separately, this is for connecting the lot to utilities
+const LOT_DEVELOPMENT_COST_PER_SQUARE_METER: f32 = 2.0;
+
 impl LotPrototype {
+    pub fn construction_cost(&self) -> f32 {
+        surface(&self.lot.area) * LOT_DEVELOPMENT_COST_PER_SQUARE_METER
+    }
+
     pub fn construct(
         &self,
         self_id: PrototypeID,
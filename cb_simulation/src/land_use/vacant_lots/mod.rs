@@ -15,7 +15,7 @@ use planning::{Prototype, PrototypeID};
 use log::{debug, error};
 const LOG_T: &str = "Vacant Lots";
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct VacantLot {
     pub id: VacantLotID,
     pub lot: Lot,
@@ -8,6 +8,11 @@ extern crate noise;
 extern crate fnv;
 extern crate roaring;
 extern crate uuid;
+extern crate bincode;
+extern crate serde_json;
+extern crate xml;
+#[macro_use]
+extern crate lazy_static;
 
 pub extern crate compact;
 #[macro_use]
@@ -16,6 +21,7 @@ pub extern crate kay;
 pub extern crate michelangelo;
 pub extern crate descartes;
 
+extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 
@@ -28,7 +34,13 @@ pub mod construction;
 pub mod economy;
 pub mod land_use;
 pub mod dimensions;
+pub mod tuning;
 pub mod environment;
+pub mod persistence;
+pub mod scenario;
+pub mod reporting;
+pub mod control;
+pub mod access;
 
 pub fn setup_common(system: &mut kay::ActorSystem) {
     for setup_fn in &[
@@ -40,6 +52,9 @@ pub fn setup_common(system: &mut kay::ActorSystem) {
         economy::setup,
         land_use::setup,
         environment::setup,
+        persistence::setup,
+        scenario::setup,
+        reporting::setup,
     ] {
         setup_fn(system)
     }
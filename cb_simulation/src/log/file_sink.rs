@@ -0,0 +1,141 @@
+use kay::RawID;
+use serde_json;
+use std::fs::{create_dir_all, read_dir, remove_file, File, OpenOptions};
+use std::io::{self, LineWriter, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Mutex;
+use time::Instant;
+use super::LogLevel;
+
+const LOG_FILE_PREFIX: &str = "log_";
+const LOG_FILE_EXTENSION: &str = ".jsonl";
+
+/// One line of a log file
+#[derive(Serialize)]
+struct FileEntry<'a> {
+    ticks: usize,
+    level: LogLevel,
+    topic: &'a str,
+    from: Option<String>,
+    message: &'a str,
+}
+
+struct RotatingLogFile {
+    directory: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+    file: LineWriter<File>,
+    bytes_written: u64,
+    // distinguishes files that were started in the same millisecond
+    n_rotations: usize,
+}
+
+impl RotatingLogFile {
+    fn open(
+        directory: PathBuf,
+        max_bytes: u64,
+        keep: usize,
+        n_rotations: usize,
+    ) -> io::Result<RotatingLogFile> {
+        create_dir_all(&directory)?;
+
+        // named after the wall clock, because simulation time starts over with every run
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        let millis = started_at.as_secs() * 1000 + u64::from(started_at.subsec_millis());
+        let path = directory.join(format!(
+            "{}{:013}_{:04}{}",
+            LOG_FILE_PREFIX, millis, n_rotations, LOG_FILE_EXTENSION
+        ));
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let bytes_written = file.metadata()?.len();
+
+        let mut log_files = read_dir(&directory)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|name| {
+                        name.starts_with(LOG_FILE_PREFIX) && name.ends_with(LOG_FILE_EXTENSION)
+                    })
+                    .unwrap_or(false)
+            })
+            .collect::<Vec<_>>();
+
+        // zero-padded timestamps make the lexical order chronological,
+        // the current file is kept even if the clock went backwards
+        log_files.retain(|log_file| *log_file != path);
+        log_files.sort();
+
+        let n_outdated = log_files.len().saturating_sub(keep - 1);
+        for outdated in &log_files[..n_outdated] {
+            remove_file(outdated)?;
+        }
+
+        Ok(RotatingLogFile {
+            directory,
+            max_bytes,
+            keep,
+            file: LineWriter::new(file),
+            bytes_written,
+            n_rotations,
+        })
+    }
+
+    fn write(&mut self, line: &str) -> io::Result<()> {
+        if self.bytes_written >= self.max_bytes {
+            *self = RotatingLogFile::open(
+                self.directory.clone(),
+                self.max_bytes,
+                self.keep,
+                self.n_rotations + 1,
+            )?;
+        }
+
+        writeln!(self.file, "{}", line)?;
+        self.bytes_written += line.len() as u64 + 1;
+        Ok(())
+    }
+}
+
+// Files can't live inside of actors, so the log of the server writes to this sink.
+// It stays empty on clients, which don't persist their logs.
+lazy_static! {
+    static ref FILE_SINK: Mutex<Option<RotatingLogFile>> = Mutex::new(None);
+}
+
+/// Starts streaming every log entry to files in `directory`, one JSON object per line.
+/// A new file is started once the current one exceeds `max_bytes`,
+/// only the `keep` newest files are kept.
+pub fn start(directory: &str, max_bytes: u64, keep: usize) -> io::Result<()> {
+    let file = RotatingLogFile::open(directory.into(), max_bytes, keep.max(1), 0)?;
+    *FILE_SINK.lock().expect("log file sink lock shouldn't be poisoned") = Some(file);
+    Ok(())
+}
+
+pub fn write(level: LogLevel, topic: &str, message: &str, from: Option<RawID>, instant: Instant) {
+    let mut sink = FILE_SINK
+        .lock()
+        .expect("log file sink lock shouldn't be poisoned");
+
+    let result = match *sink {
+        Some(ref mut file) => serde_json::to_string(&FileEntry {
+            ticks: instant.ticks(),
+            level,
+            topic,
+            from: from.map(|raw_id| format!("{:?}", raw_id)),
+            message,
+        })
+        .map_err(io::Error::from)
+        .and_then(|line| file.write(&line)),
+        None => return,
+    };
+
+    if let Err(err) = result {
+        // logging the error would only end up here again
+        println!("Couldn't write to log file, stopping file logging: {}", err);
+        *sink = None;
+    }
+}
@@ -31,13 +31,18 @@ impl TypedID for LogRecipientID {
 impl<A: Actor + LogRecipient> TraitIDFrom<A> for LogRecipientID {}
 
 impl LogRecipientID {
-    pub fn receive_newest_logs(self, entries: CVec < Entry >, text: CString, effective_last: u32, effective_text_start: u32, world: &mut World) {
+    pub fn receive_newest_logs(self, entries: CVec < Entry >, text: CString, effective_last: u32, effective_text_start: u64, world: &mut World) {
         world.send(self.as_raw(), MSG_LogRecipient_receive_newest_logs(entries, text, effective_last, effective_text_start));
     }
+    
+    pub fn receive_log_query_result(self, entries: CVec < (u32 , Entry) >, text: CString, n_matching: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_LogRecipient_receive_log_query_result(entries, text, n_matching));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<LogRecipientRepresentative>();
         system.register_trait_message::<MSG_LogRecipient_receive_newest_logs>();
+        system.register_trait_message::<MSG_LogRecipient_receive_log_query_result>();
     }
 
     pub fn register_implementor<A: Actor + LogRecipient>(system: &mut ActorSystem) {
@@ -47,11 +52,19 @@ impl LogRecipientID {
                 instance.receive_newest_logs(entries, text, effective_last, effective_text_start, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_LogRecipient_receive_log_query_result(ref entries, ref text, n_matching), instance, world| {
+                instance.receive_log_query_result(entries, text, n_matching, world); Fate::Live
+            }, false
+        );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_LogRecipient_receive_newest_logs(pub CVec < Entry >, pub CString, pub u32, pub u32);
+struct MSG_LogRecipient_receive_newest_logs(pub CVec < Entry >, pub CString, pub u32, pub u64);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LogRecipient_receive_log_query_result(pub CVec < (u32 , Entry) >, pub CString, pub u32);
 
 impl Actor for Log {
     type ID = LogID;
@@ -96,6 +109,10 @@ impl LogID {
     pub fn get_after(self, last_known: u32, max_diff: u32, recipient: LogRecipientID, world: &mut World) {
         world.send(self.as_raw(), MSG_Log_get_after(last_known, max_diff, recipient));
     }
+    
+    pub fn query(self, query: LogQuery, recipient: LogRecipientID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Log_query(query, recipient));
+    }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
@@ -104,6 +121,8 @@ struct MSG_Log_spawn(pub LogID, );
 struct MSG_Log_log(pub CString, pub CString, pub Option < RawID >, pub LogLevel);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Log_get_after(pub u32, pub u32, pub LogRecipientID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Log_query(pub LogQuery, pub LogRecipientID);
 
 
 #[allow(unused_variables)]
@@ -128,4 +147,10 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.get_after(last_known, max_diff, recipient, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Log, _, _>(
+        |&MSG_Log_query(ref query, recipient), instance, world| {
+            instance.query(query, recipient, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -1,7 +1,33 @@
 use kay::{World, ActorSystem, TypedID, RawID};
-use compact::{CVec, CString};
+use compact::{CVec, CString, COption};
+use time::{Instant, latest_instant};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-#[derive(Copy, Clone, Serialize, Deserialize)]
+pub mod file_sink;
+
+// how many entries the in-memory log keeps by default, older ones are dropped
+const DEFAULT_MAX_ENTRIES: usize = 100_000;
+// the log is trimmed in chunks of this share of its maximum size,
+// so the text doesn't have to be moved for every new entry
+const TRIM_CHUNK_FRACTION: usize = 10;
+
+// Stores the maximum + 1, 0 means "not set"
+static MAX_ENTRIES: AtomicUsize = AtomicUsize::new(0);
+
+/// Limits how many of the newest entries every log keeps in memory
+pub fn set_max_entries(max_entries: usize) {
+    MAX_ENTRIES.store(max_entries.max(1) + 1, Ordering::SeqCst);
+}
+
+fn max_entries() -> usize {
+    match MAX_ENTRIES.load(Ordering::Relaxed) {
+        0 => DEFAULT_MAX_ENTRIES,
+        max_plus_one => max_plus_one - 1,
+    }
+}
+
+/// Ordered from least to most severe
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum LogLevel {
     Debug,
     Info,
@@ -12,17 +38,92 @@ pub enum LogLevel {
 #[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Entry {
     from: Option<RawID>,
-    topic_start: u32,
-    message_start: u32,
+    // offsets into the full log text, which grows beyond 4GB in long running cities
+    topic_start: u64,
+    message_start: u64,
     message_len: u32,
     level: LogLevel,
+    instant: Instant,
 }
 
-#[derive(Compact, Clone)]
+impl Entry {
+    pub fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    /// When this entry was logged, in simulation time
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    pub fn from(&self) -> Option<RawID> {
+        self.from
+    }
+
+    /// The topic of this entry, given text that starts at `text_start` of the full log text
+    pub fn topic<'a>(&self, text: &'a str, text_start: u64) -> &'a str {
+        &text[(self.topic_start - text_start) as usize..(self.message_start - text_start) as usize]
+    }
+
+    /// The message of this entry, given text that starts at `text_start` of the full log text
+    pub fn message<'a>(&self, text: &'a str, text_start: u64) -> &'a str {
+        let start = (self.message_start - text_start) as usize;
+        &text[start..start + self.message_len as usize]
+    }
+}
+
+/// Which log entries to look for, criteria that aren't set match every entry
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct LogQuery {
+    /// Only entries at least this severe
+    pub min_level: LogLevel,
+    /// Only entries with exactly this topic, like "Households"
+    pub topic: COption<CString>,
+    /// Only entries logged by this actor
+    pub from: COption<RawID>,
+    /// Only entries logged by actors of this type, given as its short type id
+    pub from_type: COption<u16>,
+    /// Only entries logged at or after this instant
+    pub since: COption<Instant>,
+    /// Only entries logged at or before this instant
+    pub until: COption<Instant>,
+    /// How many of the newest matching entries to leave out, for paging backwards in time
+    pub skip: u32,
+    /// The most entries to return
+    pub limit: u32,
+}
+
+impl LogQuery {
+    /// Whether `entry` matches, given log text that starts at `text_start` of the full log text
+    pub fn matches(&self, entry: &Entry, text: &str, text_start: u64) -> bool {
+        entry.level >= self.min_level
+            && self
+                .topic
+                .0
+                .as_ref()
+                .map_or(true, |topic| entry.topic(text, text_start) == &topic[..])
+            && self.from.0.map_or(true, |from| entry.from == Some(from))
+            && self.from_type.0.map_or(true, |from_type| {
+                entry
+                    .from
+                    .map_or(false, |from| from.type_id.as_usize() == from_type as usize)
+            })
+            && self.since.0.map_or(true, |since| entry.instant >= since)
+            && self.until.0.map_or(true, |until| entry.instant <= until)
+    }
+}
+
+/// Keeps the newest log entries in memory. Entries are numbered and their text offsets
+/// count from the very first entry, even after older entries were dropped.
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Log {
     id: LogID,
     entries: CVec<Entry>,
     text: CString,
+    // the number of the oldest entry that is still kept
+    first_entry: u32,
+    // where the kept text starts in the full log text
+    text_start: u64,
 }
 
 pub trait LogRecipient {
@@ -31,9 +132,20 @@ pub trait LogRecipient {
         entries: &CVec<Entry>,
         text: &CString,
         effective_last: u32,
-        effective_text_start: u32,
+        effective_text_start: u64,
         world: &mut World,
     );
+
+    /// `entries` are the matching entries with their index in the whole log, oldest first.
+    /// Their text offsets refer to `text`, which only contains their topics and messages.
+    fn receive_log_query_result(
+        &mut self,
+        _entries: &CVec<(u32, Entry)>,
+        _text: &CString,
+        _n_matching: u32,
+        _world: &mut World,
+    ) {
+    }
 }
 
 impl Log {
@@ -42,7 +154,25 @@ impl Log {
             id,
             entries: CVec::new(),
             text: CString::new(),
+            first_entry: 0,
+            text_start: 0,
+        }
+    }
+
+    fn trim(&mut self) {
+        let max_entries = max_entries();
+        if self.entries.len() <= max_entries + max_entries / TRIM_CHUNK_FRACTION {
+            return;
         }
+
+        let n_dropped = self.entries.len() - max_entries;
+        let new_text_start = self.entries[n_dropped].topic_start;
+        self.entries = self.entries[n_dropped..].to_vec().into();
+        self.text = self.text[(new_text_start - self.text_start) as usize..]
+            .to_owned()
+            .into();
+        self.first_entry += n_dropped as u32;
+        self.text_start = new_text_start;
     }
 
     pub fn log(
@@ -53,9 +183,12 @@ impl Log {
         level: LogLevel,
         _: &mut World,
     ) {
-        let topic_start = self.text.len() as u32;
+        let instant = latest_instant();
+        file_sink::write(level, topic, message, from, instant);
+
+        let topic_start = self.text_start + self.text.len() as u64;
         self.text.push_str(topic);
-        let message_start = self.text.len() as u32;
+        let message_start = self.text_start + self.text.len() as u64;
         self.text.push_str(message);
         self.entries.push(Entry {
             from,
@@ -63,7 +196,9 @@ impl Log {
             message_start,
             message_len: message.len() as u32,
             level,
+            instant,
         });
+        self.trim();
     }
 
     pub fn get_after(
@@ -73,20 +208,58 @@ impl Log {
         recipient: LogRecipientID,
         world: &mut World,
     ) {
-        let effective_last = (last_known as usize).max(self.entries.len() - max_diff as usize);
-        if effective_last < self.entries.len() {
-            let entries = self.entries[effective_last..].to_vec().into();
-            let effective_text_start = self.entries[effective_last].topic_start as usize;
-            let text = self.text[effective_text_start..].to_owned().into();
+        let n_total = self.first_entry as usize + self.entries.len();
+        let effective_last = (last_known as usize)
+            .max(n_total.saturating_sub(max_diff as usize))
+            .max(self.first_entry as usize);
+        if effective_last < n_total {
+            let first_sent = effective_last - self.first_entry as usize;
+            let entries = self.entries[first_sent..].to_vec().into();
+            let effective_text_start = self.entries[first_sent].topic_start;
+            let text = self.text[(effective_text_start - self.text_start) as usize..]
+                .to_owned()
+                .into();
             recipient.receive_newest_logs(
                 entries,
                 text,
                 effective_last as u32,
-                effective_text_start as u32,
+                effective_text_start,
                 world,
             );
         }
     }
+
+    pub fn query(&mut self, query: &LogQuery, recipient: LogRecipientID, world: &mut World) {
+        let matching = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|&(_, entry)| query.matches(entry, &self.text, self.text_start))
+            .collect::<Vec<_>>();
+
+        let end = matching.len().saturating_sub(query.skip as usize);
+        let start = end.saturating_sub(query.limit as usize);
+
+        let mut entries = CVec::new();
+        let mut text = String::new();
+
+        for &(idx, entry) in &matching[start..end] {
+            let topic_start = text.len() as u64;
+            text.push_str(entry.topic(&self.text, self.text_start));
+            let message_start = text.len() as u64;
+            text.push_str(entry.message(&self.text, self.text_start));
+            entries.push((
+                self.first_entry + idx as u32,
+                Entry {
+                    topic_start,
+                    message_start,
+                    ..*entry
+                },
+            ));
+        }
+
+        recipient.receive_log_query_result(entries, text.into(), matching.len() as u32, world);
+    }
 }
 
 pub fn log<S1: Into<String>, S2: Into<String>, I: TypedID>(
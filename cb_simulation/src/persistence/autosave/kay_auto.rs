@@ -0,0 +1,66 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for Autosaver {
+    type ID = AutosaverID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct AutosaverID {
+    _raw_id: RawID
+}
+
+impl TypedID for AutosaverID {
+    type Target = Autosaver;
+
+    fn from_raw(id: RawID) -> Self {
+        AutosaverID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl AutosaverID {
+    pub fn spawn(time: TimeID, interval: Duration, world: &mut World) -> Self {
+        let id = AutosaverID::from_raw(world.allocate_instance_id::<Autosaver>());
+        let swarm = world.local_broadcast::<Autosaver>();
+        world.send(swarm, MSG_Autosaver_spawn(id, time, interval));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Autosaver_spawn(pub AutosaverID, pub TimeID, pub Duration);
+
+impl Into<SleeperID> for AutosaverID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    SleeperID::register_implementor::<Autosaver>(system);
+    system.add_spawner::<Autosaver, _, _>(
+        |&MSG_Autosaver_spawn(id, time, interval), world| {
+            Autosaver::spawn(id, time, interval, world)
+        }, false
+    );
+}
@@ -0,0 +1,105 @@
+use kay::{ActorSystem, World, TypedID};
+use std::fs::{create_dir_all, read_dir, remove_file};
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+use time::{Instant, Duration, TimeID, ScheduleID, Sleeper, SleeperID};
+
+// Writing a savegame needs the whole actor system, so the Autosaver only marks
+// an autosave as due and the server loop picks it up in between frames.
+// Stores the due instant's ticks + 1, 0 means nothing is due.
+static DUE_AUTOSAVE: AtomicUsize = AtomicUsize::new(0);
+
+const AUTOSAVE_PREFIX: &str = "autosave_";
+const AUTOSAVE_EXTENSION: &str = ".cbsave";
+
+#[derive(Compact, Clone)]
+pub struct Autosaver {
+    id: AutosaverID,
+}
+
+impl Autosaver {
+    pub fn spawn(
+        id: AutosaverID,
+        time: TimeID,
+        interval: Duration,
+        world: &mut World,
+    ) -> Autosaver {
+        time.wake_up_every(interval, id.into(), ScheduleID::new(), world);
+        Autosaver { id }
+    }
+}
+
+impl Sleeper for Autosaver {
+    fn wake(&mut self, current_instant: Instant, _: &mut World) {
+        DUE_AUTOSAVE.store(current_instant.ticks() + 1, Ordering::SeqCst);
+    }
+}
+
+/// Returns the instant of a pending autosave, if one became due since the last call
+pub fn take_due_autosave() -> Option<Instant> {
+    match DUE_AUTOSAVE.swap(0, Ordering::SeqCst) {
+        0 => None,
+        ticks_plus_one => Some(Instant::new(ticks_plus_one - 1)),
+    }
+}
+
+/// Saves to a new snapshot in `directory` and deletes all but the `keep` newest ones.
+/// Snapshots are named after the wall clock and the simulation time they were taken at.
+pub fn autosave(
+    system: &mut ActorSystem,
+    directory: &str,
+    keep: usize,
+    instant: Instant,
+) -> io::Result<()> {
+    create_dir_all(directory)?;
+
+    // sorted by the wall clock, because simulation time starts over with every new city
+    let saved_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    let millis = saved_at.as_secs() * 1000 + u64::from(saved_at.subsec_millis());
+    let path = Path::new(directory).join(format!(
+        "{}{:013}_{:010}{}",
+        AUTOSAVE_PREFIX,
+        millis,
+        instant.ticks(),
+        AUTOSAVE_EXTENSION
+    ));
+    super::save(system, &path.to_string_lossy());
+
+    let mut snapshots = read_dir(directory)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with(AUTOSAVE_PREFIX) && name.ends_with(AUTOSAVE_EXTENSION))
+                .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+
+    // zero-padded timestamps make the lexical order chronological,
+    // the new snapshot is kept even if the clock went backwards
+    snapshots.retain(|snapshot| *snapshot != path);
+    snapshots.sort();
+
+    let n_outdated = snapshots.len().saturating_sub(keep.saturating_sub(1));
+    for outdated in &snapshots[..n_outdated] {
+        remove_file(outdated)?;
+    }
+
+    Ok(())
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Autosaver>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID, interval: Duration) -> AutosaverID {
+    AutosaverID::spawn(time, interval, world)
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,780 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct PersistentID {
+    _raw_id: RawID
+}
+
+pub struct PersistentRepresentative;
+
+impl ActorOrActorTrait for PersistentRepresentative {
+    type ID = PersistentID;
+}
+
+impl TypedID for PersistentID {
+    type Target = PersistentRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        PersistentID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<A: Actor + Persistent> TraitIDFrom<A> for PersistentID {}
+
+impl PersistentID {
+    pub fn save(self, savegame: SavegameID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Persistent_save(savegame));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<PersistentRepresentative>();
+        system.register_trait_message::<MSG_Persistent_save>();
+    }
+
+    pub fn register_implementor<A: Actor + Persistent>(system: &mut ActorSystem) {
+        system.register_implementor::<A, PersistentRepresentative>();
+        system.add_handler::<A, _, _>(
+            |&MSG_Persistent_save(savegame), instance, world| {
+                instance.save(savegame, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Persistent_save(pub SavegameID);
+
+impl Actor for Savegame {
+    type ID = SavegameID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct SavegameID {
+    _raw_id: RawID
+}
+
+impl TypedID for SavegameID {
+    type Target = Savegame;
+
+    fn from_raw(id: RawID) -> Self {
+        SavegameID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl SavegameID {
+    pub fn spawn(path: CString, world: &mut World) -> Self {
+        let id = SavegameID::from_raw(world.allocate_instance_id::<Savegame>());
+        let swarm = world.local_broadcast::<Savegame>();
+        world.send(swarm, MSG_Savegame_spawn(id, path));
+        id
+    }
+    
+    pub fn store(self, actor: SavedActor, world: &mut World) {
+        world.send(self.as_raw(), MSG_Savegame_store(actor));
+    }
+    
+    pub fn finish(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Savegame_finish());
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Savegame_spawn(pub SavegameID, pub CString);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Savegame_store(pub SavedActor);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Savegame_finish();
+
+
+impl LogID {
+    pub fn restore(saved: Log, world: &mut World) -> Self {
+        let id = LogID::from_raw(allocate_saved_id::<Log>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Log>();
+        world.send(swarm, MSG_Log_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Log_restore(pub LogID, pub Log);
+
+impl Into<PersistentID> for LogID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl TimeID {
+    pub fn restore(saved: Time, world: &mut World) -> Self {
+        let id = TimeID::from_raw(allocate_saved_id::<Time>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Time>();
+        world.send(swarm, MSG_Time_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_restore(pub TimeID, pub Time);
+
+impl Into<PersistentID> for TimeID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl PlanManagerID {
+    pub fn restore(saved: PlanManager, world: &mut World) -> Self {
+        let id = PlanManagerID::from_raw(allocate_saved_id::<PlanManager>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<PlanManager>();
+        world.send(swarm, MSG_PlanManager_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_restore(pub PlanManagerID, pub PlanManager);
+
+impl Into<PersistentID> for PlanManagerID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl ConstructionID {
+    pub fn restore(saved: Construction, world: &mut World) -> Self {
+        let id = ConstructionID::from_raw(allocate_saved_id::<Construction>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Construction>();
+        world.send(swarm, MSG_Construction_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Construction_restore(pub ConstructionID, pub Construction);
+
+impl Into<PersistentID> for ConstructionID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl LaneID {
+    pub fn restore(saved: Lane, world: &mut World) -> Self {
+        let id = LaneID::from_raw(allocate_saved_id::<Lane>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Lane>();
+        world.send(swarm, MSG_Lane_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_restore(pub LaneID, pub Lane);
+
+impl Into<PersistentID> for LaneID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl SwitchLaneID {
+    pub fn restore(saved: SwitchLane, world: &mut World) -> Self {
+        let id = SwitchLaneID::from_raw(allocate_saved_id::<SwitchLane>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<SwitchLane>();
+        world.send(swarm, MSG_SwitchLane_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SwitchLane_restore(pub SwitchLaneID, pub SwitchLane);
+
+impl Into<PersistentID> for SwitchLaneID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl TripID {
+    pub fn restore(saved: Trip, world: &mut World) -> Self {
+        let id = TripID::from_raw(allocate_saved_id::<Trip>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Trip>();
+        world.send(swarm, MSG_Trip_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_restore(pub TripID, pub Trip);
+
+impl Into<PersistentID> for TripID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl TripCreatorID {
+    pub fn restore(saved: TripCreator, world: &mut World) -> Self {
+        let id = TripCreatorID::from_raw(allocate_saved_id::<TripCreator>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<TripCreator>();
+        world.send(swarm, MSG_TripCreator_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripCreator_restore(pub TripCreatorID, pub TripCreator);
+
+impl Into<PersistentID> for TripCreatorID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl FailedTripDebuggerID {
+    pub fn restore(saved: FailedTripDebugger, world: &mut World) -> Self {
+        let id = FailedTripDebuggerID::from_raw(allocate_saved_id::<FailedTripDebugger>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<FailedTripDebugger>();
+        world.send(swarm, MSG_FailedTripDebugger_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_FailedTripDebugger_restore(pub FailedTripDebuggerID, pub FailedTripDebugger);
+
+impl Into<PersistentID> for FailedTripDebuggerID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl BuildingID {
+    pub fn restore(saved: Building, world: &mut World) -> Self {
+        let id = BuildingID::from_raw(allocate_saved_id::<Building>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Building>();
+        world.send(swarm, MSG_Building_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_restore(pub BuildingID, pub Building);
+
+impl Into<PersistentID> for BuildingID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl VacantLotID {
+    pub fn restore(saved: VacantLot, world: &mut World) -> Self {
+        let id = VacantLotID::from_raw(allocate_saved_id::<VacantLot>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<VacantLot>();
+        world.send(swarm, MSG_VacantLot_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_VacantLot_restore(pub VacantLotID, pub VacantLot);
+
+impl Into<PersistentID> for VacantLotID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl PlantID {
+    pub fn restore(saved: Plant, world: &mut World) -> Self {
+        let id = PlantID::from_raw(allocate_saved_id::<Plant>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Plant>();
+        world.send(swarm, MSG_Plant_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Plant_restore(pub PlantID, pub Plant);
+
+impl Into<PersistentID> for PlantID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl MarketID {
+    pub fn restore(saved: Market, world: &mut World) -> Self {
+        let id = MarketID::from_raw(allocate_saved_id::<Market>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Market>();
+        world.send(swarm, MSG_Market_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Market_restore(pub MarketID, pub Market);
+
+impl Into<PersistentID> for MarketID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl TripCostEstimatorID {
+    pub fn restore(saved: TripCostEstimator, world: &mut World) -> Self {
+        let id = TripCostEstimatorID::from_raw(allocate_saved_id::<TripCostEstimator>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<TripCostEstimator>();
+        world.send(swarm, MSG_TripCostEstimator_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripCostEstimator_restore(pub TripCostEstimatorID, pub TripCostEstimator);
+
+impl Into<PersistentID> for TripCostEstimatorID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl TaskEndSchedulerID {
+    pub fn restore(saved: TaskEndScheduler, world: &mut World) -> Self {
+        let id = TaskEndSchedulerID::from_raw(allocate_saved_id::<TaskEndScheduler>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<TaskEndScheduler>();
+        world.send(swarm, MSG_TaskEndScheduler_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TaskEndScheduler_restore(pub TaskEndSchedulerID, pub TaskEndScheduler);
+
+impl Into<PersistentID> for TaskEndSchedulerID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl ImmigrationManagerID {
+    pub fn restore(saved: ImmigrationManager, world: &mut World) -> Self {
+        let id = ImmigrationManagerID::from_raw(allocate_saved_id::<ImmigrationManager>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<ImmigrationManager>();
+        world.send(swarm, MSG_ImmigrationManager_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ImmigrationManager_restore(pub ImmigrationManagerID, pub ImmigrationManager);
+
+impl Into<PersistentID> for ImmigrationManagerID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl DevelopmentManagerID {
+    pub fn restore(saved: DevelopmentManager, world: &mut World) -> Self {
+        let id = DevelopmentManagerID::from_raw(allocate_saved_id::<DevelopmentManager>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<DevelopmentManager>();
+        world.send(swarm, MSG_DevelopmentManager_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_DevelopmentManager_restore(pub DevelopmentManagerID, pub DevelopmentManager);
+
+impl Into<PersistentID> for DevelopmentManagerID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl TreasuryID {
+    pub fn restore(saved: Treasury, world: &mut World) -> Self {
+        let id = TreasuryID::from_raw(allocate_saved_id::<Treasury>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Treasury>();
+        world.send(swarm, MSG_Treasury_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Treasury_restore(pub TreasuryID, pub Treasury);
+
+impl Into<PersistentID> for TreasuryID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl FamilyID {
+    pub fn restore(saved: Family, world: &mut World) -> Self {
+        let id = FamilyID::from_raw(allocate_saved_id::<Family>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Family>();
+        world.send(swarm, MSG_Family_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Family_restore(pub FamilyID, pub Family);
+
+impl Into<PersistentID> for FamilyID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl GroceryShopID {
+    pub fn restore(saved: GroceryShop, world: &mut World) -> Self {
+        let id = GroceryShopID::from_raw(allocate_saved_id::<GroceryShop>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<GroceryShop>();
+        world.send(swarm, MSG_GroceryShop_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GroceryShop_restore(pub GroceryShopID, pub GroceryShop);
+
+impl Into<PersistentID> for GroceryShopID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl GrainFarmID {
+    pub fn restore(saved: GrainFarm, world: &mut World) -> Self {
+        let id = GrainFarmID::from_raw(allocate_saved_id::<GrainFarm>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<GrainFarm>();
+        world.send(swarm, MSG_GrainFarm_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_GrainFarm_restore(pub GrainFarmID, pub GrainFarm);
+
+impl Into<PersistentID> for GrainFarmID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl CowFarmID {
+    pub fn restore(saved: CowFarm, world: &mut World) -> Self {
+        let id = CowFarmID::from_raw(allocate_saved_id::<CowFarm>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<CowFarm>();
+        world.send(swarm, MSG_CowFarm_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CowFarm_restore(pub CowFarmID, pub CowFarm);
+
+impl Into<PersistentID> for CowFarmID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl VegetableFarmID {
+    pub fn restore(saved: VegetableFarm, world: &mut World) -> Self {
+        let id = VegetableFarmID::from_raw(allocate_saved_id::<VegetableFarm>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<VegetableFarm>();
+        world.send(swarm, MSG_VegetableFarm_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_VegetableFarm_restore(pub VegetableFarmID, pub VegetableFarm);
+
+impl Into<PersistentID> for VegetableFarmID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl MillID {
+    pub fn restore(saved: Mill, world: &mut World) -> Self {
+        let id = MillID::from_raw(allocate_saved_id::<Mill>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Mill>();
+        world.send(swarm, MSG_Mill_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Mill_restore(pub MillID, pub Mill);
+
+impl Into<PersistentID> for MillID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl BakeryID {
+    pub fn restore(saved: Bakery, world: &mut World) -> Self {
+        let id = BakeryID::from_raw(allocate_saved_id::<Bakery>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<Bakery>();
+        world.send(swarm, MSG_Bakery_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Bakery_restore(pub BakeryID, pub Bakery);
+
+impl Into<PersistentID> for BakeryID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+
+impl NeighboringTownTradeID {
+    pub fn restore(saved: NeighboringTownTrade, world: &mut World) -> Self {
+        let id = NeighboringTownTradeID::from_raw(allocate_saved_id::<NeighboringTownTrade>(saved.id().as_raw(), world));
+        let swarm = world.local_broadcast::<NeighboringTownTrade>();
+        world.send(swarm, MSG_NeighboringTownTrade_restore(id, saved));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NeighboringTownTrade_restore(pub NeighboringTownTradeID, pub NeighboringTownTrade);
+
+impl Into<PersistentID> for NeighboringTownTradeID {
+    fn into(self) -> PersistentID {
+        PersistentID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    PersistentID::register_trait(system);
+    
+    system.add_spawner::<Savegame, _, _>(
+        |&MSG_Savegame_spawn(id, ref path), world| {
+            Savegame::spawn(id, path, world)
+        }, false
+    );
+    
+    system.add_handler::<Savegame, _, _>(
+        |&MSG_Savegame_store(ref actor), instance, world| {
+            instance.store(actor, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Savegame, _, _>(
+        |&MSG_Savegame_finish(), instance, world| {
+            instance.finish(world)
+        }, false
+    );
+    PersistentID::register_implementor::<Log>(system);
+    system.add_spawner::<Log, _, _>(
+        |&MSG_Log_restore(id, ref saved), world| {
+            Log::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Time>(system);
+    system.add_spawner::<Time, _, _>(
+        |&MSG_Time_restore(id, ref saved), world| {
+            Time::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<PlanManager>(system);
+    system.add_spawner::<PlanManager, _, _>(
+        |&MSG_PlanManager_restore(id, ref saved), world| {
+            PlanManager::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Construction>(system);
+    system.add_spawner::<Construction, _, _>(
+        |&MSG_Construction_restore(id, ref saved), world| {
+            Construction::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Lane>(system);
+    system.add_spawner::<Lane, _, _>(
+        |&MSG_Lane_restore(id, ref saved), world| {
+            Lane::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<SwitchLane>(system);
+    system.add_spawner::<SwitchLane, _, _>(
+        |&MSG_SwitchLane_restore(id, ref saved), world| {
+            SwitchLane::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Trip>(system);
+    system.add_spawner::<Trip, _, _>(
+        |&MSG_Trip_restore(id, ref saved), world| {
+            Trip::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<TripCreator>(system);
+    system.add_spawner::<TripCreator, _, _>(
+        |&MSG_TripCreator_restore(id, ref saved), world| {
+            TripCreator::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<FailedTripDebugger>(system);
+    system.add_spawner::<FailedTripDebugger, _, _>(
+        |&MSG_FailedTripDebugger_restore(id, ref saved), world| {
+            FailedTripDebugger::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Building>(system);
+    system.add_spawner::<Building, _, _>(
+        |&MSG_Building_restore(id, ref saved), world| {
+            Building::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<VacantLot>(system);
+    system.add_spawner::<VacantLot, _, _>(
+        |&MSG_VacantLot_restore(id, ref saved), world| {
+            VacantLot::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Plant>(system);
+    system.add_spawner::<Plant, _, _>(
+        |&MSG_Plant_restore(id, ref saved), world| {
+            Plant::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Market>(system);
+    system.add_spawner::<Market, _, _>(
+        |&MSG_Market_restore(id, ref saved), world| {
+            Market::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<TripCostEstimator>(system);
+    system.add_spawner::<TripCostEstimator, _, _>(
+        |&MSG_TripCostEstimator_restore(id, ref saved), world| {
+            TripCostEstimator::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<TaskEndScheduler>(system);
+    system.add_spawner::<TaskEndScheduler, _, _>(
+        |&MSG_TaskEndScheduler_restore(id, ref saved), world| {
+            TaskEndScheduler::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<ImmigrationManager>(system);
+    system.add_spawner::<ImmigrationManager, _, _>(
+        |&MSG_ImmigrationManager_restore(id, ref saved), world| {
+            ImmigrationManager::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<DevelopmentManager>(system);
+    system.add_spawner::<DevelopmentManager, _, _>(
+        |&MSG_DevelopmentManager_restore(id, ref saved), world| {
+            DevelopmentManager::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Treasury>(system);
+    system.add_spawner::<Treasury, _, _>(
+        |&MSG_Treasury_restore(id, ref saved), world| {
+            Treasury::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Family>(system);
+    system.add_spawner::<Family, _, _>(
+        |&MSG_Family_restore(id, ref saved), world| {
+            Family::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<GroceryShop>(system);
+    system.add_spawner::<GroceryShop, _, _>(
+        |&MSG_GroceryShop_restore(id, ref saved), world| {
+            GroceryShop::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<GrainFarm>(system);
+    system.add_spawner::<GrainFarm, _, _>(
+        |&MSG_GrainFarm_restore(id, ref saved), world| {
+            GrainFarm::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<CowFarm>(system);
+    system.add_spawner::<CowFarm, _, _>(
+        |&MSG_CowFarm_restore(id, ref saved), world| {
+            CowFarm::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<VegetableFarm>(system);
+    system.add_spawner::<VegetableFarm, _, _>(
+        |&MSG_VegetableFarm_restore(id, ref saved), world| {
+            VegetableFarm::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Mill>(system);
+    system.add_spawner::<Mill, _, _>(
+        |&MSG_Mill_restore(id, ref saved), world| {
+            Mill::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<Bakery>(system);
+    system.add_spawner::<Bakery, _, _>(
+        |&MSG_Bakery_restore(id, ref saved), world| {
+            Bakery::restore(id, saved, world)
+        }, false
+    );
+    PersistentID::register_implementor::<NeighboringTownTrade>(system);
+    system.add_spawner::<NeighboringTownTrade, _, _>(
+        |&MSG_NeighboringTownTrade_restore(id, ref saved), world| {
+            NeighboringTownTrade::restore(id, saved, world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,770 @@
+use kay::{ActorSystem, World, Fate, TypedID, Actor, RawID};
+use compact::CString;
+use bincode;
+use std::fs::{File, rename};
+use std::io::{BufReader, BufWriter, Write};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use serde::Serialize;
+
+use log::{Log, LogID, info, error};
+use time::{Time, TimeID};
+use planning::{PlanManager, PlanManagerID};
+use construction::{Construction, ConstructionID};
+use transport::lane::{Lane, LaneID, SwitchLane, SwitchLaneID};
+use transport::pathfinding::trip::{Trip, TripID, TripCreator, TripCreatorID, FailedTripDebugger,
+FailedTripDebuggerID};
+use land_use::buildings::{Building, BuildingID};
+use land_use::vacant_lots::{VacantLot, VacantLotID};
+use environment::vegetation::{Plant, PlantID};
+use economy::market::{Market, MarketID, TripCostEstimator, TripCostEstimatorID};
+use economy::households::tasks::{TaskEndScheduler, TaskEndSchedulerID};
+use economy::immigration_and_development::{ImmigrationManager, ImmigrationManagerID,
+DevelopmentManager, DevelopmentManagerID};
+use economy::treasury::{Treasury, TreasuryID};
+use economy::households::household_kinds::family::{Family, FamilyID};
+use economy::households::household_kinds::grocery_shop::{GroceryShop, GroceryShopID};
+use economy::households::household_kinds::grain_farm::{GrainFarm, GrainFarmID};
+use economy::households::household_kinds::cow_farm::{CowFarm, CowFarmID};
+use economy::households::household_kinds::vegetable_farm::{VegetableFarm, VegetableFarmID};
+use economy::households::household_kinds::mill::{Mill, MillID};
+use economy::households::household_kinds::bakery::{Bakery, BakeryID};
+use economy::households::household_kinds::neighboring_town_trade::{NeighboringTownTrade,
+NeighboringTownTradeID};
+
+pub mod autosave;
+
+const LOG_T: &str = "Persistence";
+
+// bump this whenever the layout of any saved actor changes
+pub const SAVEGAME_VERSION: u32 = 16;
+
+pub trait Persistent {
+    fn save(&mut self, savegame: SavegameID, world: &mut World);
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub enum SavedActor {
+    Log(Log),
+    Time(Time),
+    PlanManager(PlanManager),
+    Construction(Construction),
+    Lane(Lane),
+    SwitchLane(SwitchLane),
+    Trip(Trip),
+    TripCreator(TripCreator),
+    FailedTripDebugger(FailedTripDebugger),
+    Building(Building),
+    VacantLot(VacantLot),
+    Plant(Plant),
+    Market(Market),
+    TripCostEstimator(TripCostEstimator),
+    TaskEndScheduler(TaskEndScheduler),
+    ImmigrationManager(ImmigrationManager),
+    DevelopmentManager(DevelopmentManager),
+    Treasury(Treasury),
+    Family(Family),
+    GroceryShop(GroceryShop),
+    GrainFarm(GrainFarm),
+    CowFarm(CowFarm),
+    VegetableFarm(VegetableFarm),
+    Mill(Mill),
+    Bakery(Bakery),
+    NeighboringTownTrade(NeighboringTownTrade),
+}
+
+impl SavedActor {
+    /// The ID the actor had when it was saved
+    fn saved_id(&self) -> RawID {
+        match *self {
+            SavedActor::Log(ref actor) => actor.id().as_raw(),
+            SavedActor::Time(ref actor) => actor.id().as_raw(),
+            SavedActor::PlanManager(ref actor) => actor.id().as_raw(),
+            SavedActor::Construction(ref actor) => actor.id().as_raw(),
+            SavedActor::Lane(ref actor) => actor.id().as_raw(),
+            SavedActor::SwitchLane(ref actor) => actor.id().as_raw(),
+            SavedActor::Trip(ref actor) => actor.id().as_raw(),
+            SavedActor::TripCreator(ref actor) => actor.id().as_raw(),
+            SavedActor::FailedTripDebugger(ref actor) => actor.id().as_raw(),
+            SavedActor::Building(ref actor) => actor.id().as_raw(),
+            SavedActor::VacantLot(ref actor) => actor.id().as_raw(),
+            SavedActor::Plant(ref actor) => actor.id().as_raw(),
+            SavedActor::Market(ref actor) => actor.id().as_raw(),
+            SavedActor::TripCostEstimator(ref actor) => actor.id().as_raw(),
+            SavedActor::TaskEndScheduler(ref actor) => actor.id().as_raw(),
+            SavedActor::ImmigrationManager(ref actor) => actor.id().as_raw(),
+            SavedActor::DevelopmentManager(ref actor) => actor.id().as_raw(),
+            SavedActor::Treasury(ref actor) => actor.id().as_raw(),
+            SavedActor::Family(ref actor) => actor.id().as_raw(),
+            SavedActor::GroceryShop(ref actor) => actor.id().as_raw(),
+            SavedActor::GrainFarm(ref actor) => actor.id().as_raw(),
+            SavedActor::CowFarm(ref actor) => actor.id().as_raw(),
+            SavedActor::VegetableFarm(ref actor) => actor.id().as_raw(),
+            SavedActor::Mill(ref actor) => actor.id().as_raw(),
+            SavedActor::Bakery(ref actor) => actor.id().as_raw(),
+            SavedActor::NeighboringTownTrade(ref actor) => actor.id().as_raw(),
+        }
+    }
+
+    /// Restores the actor and returns the ID it got
+    fn restore(self, n_allocated: &mut HashMap<usize, u32>, world: &mut World) -> RawID {
+        let saved_id = self.saved_id();
+        match self {
+            SavedActor::Log(actor) => {
+                reserve_ids_up_to::<Log>(saved_id, n_allocated, world);
+                LogID::restore(actor, world).as_raw()
+            }
+            SavedActor::Time(actor) => {
+                reserve_ids_up_to::<Time>(saved_id, n_allocated, world);
+                TimeID::restore(actor, world).as_raw()
+            }
+            SavedActor::PlanManager(actor) => {
+                reserve_ids_up_to::<PlanManager>(saved_id, n_allocated, world);
+                PlanManagerID::restore(actor, world).as_raw()
+            }
+            SavedActor::Construction(actor) => {
+                reserve_ids_up_to::<Construction>(saved_id, n_allocated, world);
+                ConstructionID::restore(actor, world).as_raw()
+            }
+            SavedActor::Lane(actor) => {
+                reserve_ids_up_to::<Lane>(saved_id, n_allocated, world);
+                LaneID::restore(actor, world).as_raw()
+            }
+            SavedActor::SwitchLane(actor) => {
+                reserve_ids_up_to::<SwitchLane>(saved_id, n_allocated, world);
+                SwitchLaneID::restore(actor, world).as_raw()
+            }
+            SavedActor::Trip(actor) => {
+                reserve_ids_up_to::<Trip>(saved_id, n_allocated, world);
+                TripID::restore(actor, world).as_raw()
+            }
+            SavedActor::TripCreator(actor) => {
+                reserve_ids_up_to::<TripCreator>(saved_id, n_allocated, world);
+                TripCreatorID::restore(actor, world).as_raw()
+            }
+            SavedActor::FailedTripDebugger(actor) => {
+                reserve_ids_up_to::<FailedTripDebugger>(saved_id, n_allocated, world);
+                FailedTripDebuggerID::restore(actor, world).as_raw()
+            }
+            SavedActor::Building(actor) => {
+                reserve_ids_up_to::<Building>(saved_id, n_allocated, world);
+                BuildingID::restore(actor, world).as_raw()
+            }
+            SavedActor::VacantLot(actor) => {
+                reserve_ids_up_to::<VacantLot>(saved_id, n_allocated, world);
+                VacantLotID::restore(actor, world).as_raw()
+            }
+            SavedActor::Plant(actor) => {
+                reserve_ids_up_to::<Plant>(saved_id, n_allocated, world);
+                PlantID::restore(actor, world).as_raw()
+            }
+            SavedActor::Market(actor) => {
+                reserve_ids_up_to::<Market>(saved_id, n_allocated, world);
+                MarketID::restore(actor, world).as_raw()
+            }
+            SavedActor::TripCostEstimator(actor) => {
+                reserve_ids_up_to::<TripCostEstimator>(saved_id, n_allocated, world);
+                TripCostEstimatorID::restore(actor, world).as_raw()
+            }
+            SavedActor::TaskEndScheduler(actor) => {
+                reserve_ids_up_to::<TaskEndScheduler>(saved_id, n_allocated, world);
+                TaskEndSchedulerID::restore(actor, world).as_raw()
+            }
+            SavedActor::ImmigrationManager(actor) => {
+                reserve_ids_up_to::<ImmigrationManager>(saved_id, n_allocated, world);
+                ImmigrationManagerID::restore(actor, world).as_raw()
+            }
+            SavedActor::DevelopmentManager(actor) => {
+                reserve_ids_up_to::<DevelopmentManager>(saved_id, n_allocated, world);
+                DevelopmentManagerID::restore(actor, world).as_raw()
+            }
+            SavedActor::Treasury(actor) => {
+                reserve_ids_up_to::<Treasury>(saved_id, n_allocated, world);
+                TreasuryID::restore(actor, world).as_raw()
+            }
+            SavedActor::Family(actor) => {
+                reserve_ids_up_to::<Family>(saved_id, n_allocated, world);
+                FamilyID::restore(actor, world).as_raw()
+            }
+            SavedActor::GroceryShop(actor) => {
+                reserve_ids_up_to::<GroceryShop>(saved_id, n_allocated, world);
+                GroceryShopID::restore(actor, world).as_raw()
+            }
+            SavedActor::GrainFarm(actor) => {
+                reserve_ids_up_to::<GrainFarm>(saved_id, n_allocated, world);
+                GrainFarmID::restore(actor, world).as_raw()
+            }
+            SavedActor::CowFarm(actor) => {
+                reserve_ids_up_to::<CowFarm>(saved_id, n_allocated, world);
+                CowFarmID::restore(actor, world).as_raw()
+            }
+            SavedActor::VegetableFarm(actor) => {
+                reserve_ids_up_to::<VegetableFarm>(saved_id, n_allocated, world);
+                VegetableFarmID::restore(actor, world).as_raw()
+            }
+            SavedActor::Mill(actor) => {
+                reserve_ids_up_to::<Mill>(saved_id, n_allocated, world);
+                MillID::restore(actor, world).as_raw()
+            }
+            SavedActor::Bakery(actor) => {
+                reserve_ids_up_to::<Bakery>(saved_id, n_allocated, world);
+                BakeryID::restore(actor, world).as_raw()
+            }
+            SavedActor::NeighboringTownTrade(actor) => {
+                reserve_ids_up_to::<NeighboringTownTrade>(saved_id, n_allocated, world);
+                NeighboringTownTradeID::restore(actor, world).as_raw()
+            }
+        }
+    }
+}
+
+// Restored actors keep the IDs they were saved with, because other restored actors refer
+// to them by these. The swarms of a fresh actor system hand out instance IDs in increasing
+// order, so also allocating the IDs of actors that died before the game was saved makes them
+// hand out exactly the saved ID to each restored actor, and never to newly spawned ones.
+fn reserve_ids_up_to<A: Actor>(
+    saved_id: RawID,
+    n_allocated: &mut HashMap<usize, u32>,
+    world: &mut World,
+) {
+    let n_allocated = n_allocated.entry(saved_id.type_id.as_usize()).or_insert(0);
+    while *n_allocated < saved_id.instance_id {
+        world.allocate_instance_id::<A>();
+        *n_allocated += 1;
+    }
+    // restoring the actor allocates the saved ID itself
+    *n_allocated += 1;
+}
+
+// Fresh IDs start out at version 0, but the saved references to an actor carry
+// the version its ID had when it was saved, after being reused by earlier actors.
+fn allocate_saved_id<A: Actor>(saved_id: RawID, world: &mut World) -> RawID {
+    let mut id = world.allocate_instance_id::<A>();
+    id.version = saved_id.version;
+    id
+}
+
+impl Persistent for Log {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Log(self.clone()), world);
+    }
+}
+
+impl Persistent for Time {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Time(self.clone()), world);
+    }
+}
+
+impl Persistent for PlanManager {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::PlanManager(self.clone()), world);
+    }
+}
+
+impl Persistent for Construction {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Construction(self.clone()), world);
+    }
+}
+
+impl Persistent for Lane {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Lane(self.clone()), world);
+    }
+}
+
+impl Persistent for SwitchLane {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::SwitchLane(self.clone()), world);
+    }
+}
+
+impl Persistent for Trip {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Trip(self.clone()), world);
+    }
+}
+
+impl Persistent for TripCreator {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::TripCreator(self.clone()), world);
+    }
+}
+
+impl Persistent for FailedTripDebugger {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::FailedTripDebugger(self.clone()), world);
+    }
+}
+
+impl Persistent for Building {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Building(self.clone()), world);
+    }
+}
+
+impl Persistent for VacantLot {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::VacantLot(self.clone()), world);
+    }
+}
+
+impl Persistent for Plant {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Plant(self.clone()), world);
+    }
+}
+
+impl Persistent for Market {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Market(self.clone()), world);
+    }
+}
+
+impl Persistent for TripCostEstimator {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::TripCostEstimator(self.clone()), world);
+    }
+}
+
+impl Persistent for TaskEndScheduler {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::TaskEndScheduler(self.clone()), world);
+    }
+}
+
+impl Persistent for ImmigrationManager {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::ImmigrationManager(self.clone()), world);
+    }
+}
+
+impl Persistent for DevelopmentManager {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::DevelopmentManager(self.clone()), world);
+    }
+}
+
+impl Persistent for Treasury {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Treasury(self.clone()), world);
+    }
+}
+
+impl Persistent for Family {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Family(self.clone()), world);
+    }
+}
+
+impl Persistent for GroceryShop {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::GroceryShop(self.clone()), world);
+    }
+}
+
+impl Persistent for GrainFarm {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::GrainFarm(self.clone()), world);
+    }
+}
+
+impl Persistent for CowFarm {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::CowFarm(self.clone()), world);
+    }
+}
+
+impl Persistent for VegetableFarm {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::VegetableFarm(self.clone()), world);
+    }
+}
+
+impl Persistent for Mill {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Mill(self.clone()), world);
+    }
+}
+
+impl Persistent for Bakery {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::Bakery(self.clone()), world);
+    }
+}
+
+impl Persistent for NeighboringTownTrade {
+    fn save(&mut self, savegame: SavegameID, world: &mut World) {
+        savegame.store(SavedActor::NeighboringTownTrade(self.clone()), world);
+    }
+}
+
+// `load` makes sure the freshly allocated ID is the one stored inside of the saved actor,
+// so all references between restored actors stay valid
+
+impl Log {
+    pub fn restore(_id: LogID, saved: &Log, _: &mut World) -> Log {
+        saved.clone()
+    }
+}
+
+impl Time {
+    pub fn restore(_id: TimeID, saved: &Time, _: &mut World) -> Time {
+        saved.mirror_current_instant();
+        saved.clone()
+    }
+}
+
+impl PlanManager {
+    pub fn restore(_id: PlanManagerID, saved: &PlanManager, _: &mut World) -> PlanManager {
+        saved.clone()
+    }
+}
+
+impl Construction {
+    pub fn restore(_id: ConstructionID, saved: &Construction, _: &mut World) -> Construction {
+        saved.clone()
+    }
+}
+
+impl Lane {
+    pub fn restore(_id: LaneID, saved: &Lane, _: &mut World) -> Lane {
+        saved.clone()
+    }
+}
+
+impl SwitchLane {
+    pub fn restore(_id: SwitchLaneID, saved: &SwitchLane, _: &mut World) -> SwitchLane {
+        saved.clone()
+    }
+}
+
+impl Trip {
+    pub fn restore(_id: TripID, saved: &Trip, _: &mut World) -> Trip {
+        ::transport::pathfinding::trip::count_restored_trip();
+        saved.clone()
+    }
+}
+
+impl TripCreator {
+    pub fn restore(_id: TripCreatorID, saved: &TripCreator, _: &mut World) -> TripCreator {
+        saved.clone()
+    }
+}
+
+impl FailedTripDebugger {
+    pub fn restore(
+        _id: FailedTripDebuggerID,
+        saved: &FailedTripDebugger,
+        _: &mut World,
+    ) -> FailedTripDebugger {
+        saved.clone()
+    }
+}
+
+impl Building {
+    pub fn restore(_id: BuildingID, saved: &Building, _: &mut World) -> Building {
+        saved.clone()
+    }
+}
+
+impl VacantLot {
+    pub fn restore(_id: VacantLotID, saved: &VacantLot, _: &mut World) -> VacantLot {
+        saved.clone()
+    }
+}
+
+impl Plant {
+    pub fn restore(_id: PlantID, saved: &Plant, _: &mut World) -> Plant {
+        saved.clone()
+    }
+}
+
+impl Market {
+    pub fn restore(_id: MarketID, saved: &Market, _: &mut World) -> Market {
+        saved.clone()
+    }
+}
+
+impl TripCostEstimator {
+    pub fn restore(
+        _id: TripCostEstimatorID,
+        saved: &TripCostEstimator,
+        _: &mut World,
+    ) -> TripCostEstimator {
+        saved.clone()
+    }
+}
+
+impl TaskEndScheduler {
+    pub fn restore(
+        _id: TaskEndSchedulerID,
+        saved: &TaskEndScheduler,
+        _: &mut World,
+    ) -> TaskEndScheduler {
+        saved.clone()
+    }
+}
+
+impl ImmigrationManager {
+    pub fn restore(
+        _id: ImmigrationManagerID,
+        saved: &ImmigrationManager,
+        _: &mut World,
+    ) -> ImmigrationManager {
+        saved.clone()
+    }
+}
+
+impl DevelopmentManager {
+    pub fn restore(
+        _id: DevelopmentManagerID,
+        saved: &DevelopmentManager,
+        _: &mut World,
+    ) -> DevelopmentManager {
+        saved.clone()
+    }
+}
+
+impl Treasury {
+    pub fn restore(_id: TreasuryID, saved: &Treasury, _: &mut World) -> Treasury {
+        saved.clone()
+    }
+}
+
+impl Family {
+    pub fn restore(_id: FamilyID, saved: &Family, _: &mut World) -> Family {
+        saved.clone()
+    }
+}
+
+impl GroceryShop {
+    pub fn restore(_id: GroceryShopID, saved: &GroceryShop, _: &mut World) -> GroceryShop {
+        saved.clone()
+    }
+}
+
+impl GrainFarm {
+    pub fn restore(_id: GrainFarmID, saved: &GrainFarm, _: &mut World) -> GrainFarm {
+        saved.clone()
+    }
+}
+
+impl CowFarm {
+    pub fn restore(_id: CowFarmID, saved: &CowFarm, _: &mut World) -> CowFarm {
+        saved.clone()
+    }
+}
+
+impl VegetableFarm {
+    pub fn restore(_id: VegetableFarmID, saved: &VegetableFarm, _: &mut World) -> VegetableFarm {
+        saved.clone()
+    }
+}
+
+impl Mill {
+    pub fn restore(_id: MillID, saved: &Mill, _: &mut World) -> Mill {
+        saved.clone()
+    }
+}
+
+impl Bakery {
+    pub fn restore(_id: BakeryID, saved: &Bakery, _: &mut World) -> Bakery {
+        saved.clone()
+    }
+}
+
+impl NeighboringTownTrade {
+    pub fn restore(
+        _id: NeighboringTownTradeID,
+        saved: &NeighboringTownTrade,
+        _: &mut World,
+    ) -> NeighboringTownTrade {
+        saved.clone()
+    }
+}
+
+// Savegames are a version header followed by one `Some(SavedActor)` record per actor,
+// terminated by a `None` record. Records are appended to a temporary file as they arrive,
+// which only replaces the actual savegame once all actors reported back.
+
+// Actors can't own a file, so the temporary file of the savegame that is being written
+// stays open here. Saving blocks the simulation, so there is never more than one.
+lazy_static! {
+    static ref SAVEGAME_FILE: Mutex<Option<BufWriter<File>>> = Mutex::new(None);
+}
+
+#[derive(Compact, Clone)]
+pub struct Savegame {
+    id: SavegameID,
+    path: CString,
+    n_saved: u32,
+    failed: bool,
+}
+
+fn temporary_path(path: &str) -> String {
+    format!("{}.tmp", path)
+}
+
+fn write_record<T: Serialize>(record: &T) -> bincode::Result<()> {
+    match *SAVEGAME_FILE
+        .lock()
+        .expect("savegame file lock shouldn't be poisoned")
+    {
+        Some(ref mut writer) => bincode::serialize_into(writer, record),
+        None => Err(Box::new(bincode::ErrorKind::Custom(
+            "the savegame isn't open".to_owned(),
+        ))),
+    }
+}
+
+impl Savegame {
+    pub fn spawn(id: SavegameID, path: &CString, world: &mut World) -> Savegame {
+        let header_written = File::create(temporary_path(path))
+            .map_err(bincode::Error::from)
+            .and_then(|file| {
+                *SAVEGAME_FILE
+                    .lock()
+                    .expect("savegame file lock shouldn't be poisoned") =
+                    Some(BufWriter::new(file));
+                write_record(&SAVEGAME_VERSION)
+            });
+
+        if let Err(ref err) = header_written {
+            error(
+                LOG_T,
+                format!("Couldn't create savegame {}: {}", &**path, err),
+                id,
+                world,
+            );
+        }
+
+        Savegame {
+            id,
+            path: path.clone(),
+            n_saved: 0,
+            failed: header_written.is_err(),
+        }
+    }
+
+    pub fn store(&mut self, actor: &SavedActor, world: &mut World) {
+        if !self.failed {
+            if let Err(err) = write_record(&Some(actor)) {
+                error(LOG_T, format!("Couldn't write savegame: {}", err), self.id, world);
+                self.failed = true;
+            } else {
+                self.n_saved += 1;
+            }
+        }
+    }
+
+    pub fn finish(&mut self, world: &mut World) -> Fate {
+        // closes the temporary file, also if writing it failed
+        let writer = SAVEGAME_FILE
+            .lock()
+            .expect("savegame file lock shouldn't be poisoned")
+            .take();
+
+        if !self.failed {
+            let mut writer = writer.expect("savegames that didn't fail stay open");
+            let finished = bincode::serialize_into(&mut writer, &None::<SavedActor>)
+                .and_then(|()| {
+                    writer.flush()?;
+                    drop(writer);
+                    rename(temporary_path(&self.path), &*self.path)?;
+                    Ok(())
+                });
+
+            match finished {
+                Ok(()) => info(
+                    LOG_T,
+                    format!("Saved {} actors to {}", self.n_saved, &*self.path),
+                    self.id,
+                    world,
+                ),
+                Err(err) => error(
+                    LOG_T,
+                    format!("Couldn't write savegame: {}", err),
+                    self.id,
+                    world,
+                ),
+            }
+        }
+
+        Fate::Die
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Savegame>();
+    auto_setup(system);
+    autosave::setup(system);
+}
+
+/// Asks every local actor to persist itself and blocks until the savegame is written
+pub fn save(system: &mut ActorSystem, path: &str) {
+    let world = &mut system.world();
+    let savegame = SavegameID::spawn(path.to_owned().into(), world);
+    PersistentID::local_broadcast(world).save(savegame, world);
+    system.process_all_messages();
+    savegame.finish(world);
+    system.process_all_messages();
+}
+
+/// Respawns all actors of a savegame, returns how many were restored.
+/// Needs to happen before any of the saved kinds of actors are spawned otherwise.
+pub fn load(path: &str, world: &mut World) -> bincode::Result<usize> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let version: u32 = bincode::deserialize_from(&mut reader)?;
+    if version != SAVEGAME_VERSION {
+        return Err(Box::new(bincode::ErrorKind::Custom(format!(
+            "Savegame version {} is not supported (expected {})",
+            version, SAVEGAME_VERSION
+        ))));
+    }
+
+    let mut saved_actors = Vec::new();
+    while let Some(saved_actor) = bincode::deserialize_from::<_, Option<SavedActor>>(&mut reader)? {
+        saved_actors.push(saved_actor);
+    }
+
+    // IDs can only be reserved in increasing order, see `reserve_ids_up_to`
+    saved_actors.sort_by_key(|saved_actor| {
+        let saved_id = saved_actor.saved_id();
+        (saved_id.type_id.as_usize(), saved_id.instance_id)
+    });
+
+    // actors that aren't saved, like the Autosaver, may still have had wake ups pending.
+    // Their IDs could be given to different actors after loading, so forget those.
+    let saved_ids = saved_actors
+        .iter()
+        .map(|saved_actor| saved_actor.saved_id())
+        .collect::<HashSet<_>>();
+    for saved_actor in &mut saved_actors {
+        if let SavedActor::Time(ref mut time) = *saved_actor {
+            time.retain_sleepers(|sleeper| saved_ids.contains(&sleeper));
+        }
+    }
+
+    let n_restored = saved_actors.len();
+    let mut n_allocated = HashMap::new();
+    for saved_actor in saved_actors {
+        let saved_id = saved_actor.saved_id();
+        let restored_id = saved_actor.restore(&mut n_allocated, world);
+        if restored_id.instance_id != saved_id.instance_id
+            || restored_id.version != saved_id.version
+        {
+            return Err(Box::new(bincode::ErrorKind::Custom(format!(
+                "Couldn't restore actor {:?} under its saved ID, got {:?}",
+                saved_id, restored_id
+            ))));
+        }
+    }
+
+    Ok(n_restored)
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,44 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl PlanManagerID {
+    pub fn get_conflicts(self, ui: PlanningUIID, project_id: ProjectID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_get_conflicts(ui, project_id));
+    }
+    
+    pub fn rebase(self, project_id: ProjectID, resolutions: CVec < (GestureID , GestureResolution) >, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_rebase(project_id, resolutions, session));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_get_conflicts(pub PlanningUIID, pub ProjectID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_rebase(pub ProjectID, pub CVec < (GestureID , GestureResolution) >, pub SessionID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_get_conflicts(ui, project_id), instance, world| {
+            instance.get_conflicts(ui, project_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_rebase(project_id, ref resolutions, session), instance, world| {
+            instance.rebase(project_id, resolutions, session, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,213 @@
+use kay::World;
+use compact::{CVec, COption};
+use std::collections::HashSet;
+use access::SessionID;
+use planning::{PlanManager, PlanManagerID, ProjectID, GestureID, VersionedGesture, PlanResult};
+use planning::ui::PlanningUIID;
+use planning::journal::JournalCommand;
+use log::{error, info, warn};
+const LOG_T: &str = "Planning Conflicts";
+
+/// What could go wrong when implementing a project, given the master plan and the other
+/// projects that haven't been implemented yet
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct ProjectConflicts {
+    /// Gestures the project changes that were also changed in the master plan since the
+    /// project was started or rebased. Implementing would overwrite those changes.
+    pub outdated_gestures: CVec<GestureID>,
+    /// Gestures the project changes that another project changes as well
+    pub shared_gestures: CVec<(ProjectID, GestureID)>,
+    /// Cells of the prototype grid where the result of the project and the result of
+    /// another project both differ from the master result
+    pub overlapping_cells: CVec<(ProjectID, (i32, i32))>,
+}
+
+/// How a rebase deals with a gesture that was changed both in the project and in the
+/// master plan. The master plan only knows the latest version of each gesture, so the two
+/// versions can't be merged automatically and one of them has to be chosen.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum GestureResolution {
+    /// The version of the project replaces the one of the master plan when implementing
+    KeepProject,
+    /// The project drops its changes to the gesture, keeping the one of the master plan
+    TakeMaster,
+}
+
+impl ProjectConflicts {
+    pub fn is_empty(&self) -> bool {
+        self.outdated_gestures.is_empty()
+            && self.shared_gestures.is_empty()
+            && self.overlapping_cells.is_empty()
+    }
+}
+
+impl PlanManager {
+    /// Gestures the project changes that changed in the master plan after it was based on it
+    pub fn outdated_gestures(&self, project_id: ProjectID) -> Vec<GestureID> {
+        let project = match self.projects.get(project_id) {
+            Some(project) => project,
+            None => return Vec::new(),
+        };
+
+        project
+            .touched_gestures()
+            .into_iter()
+            .filter(|gesture_id| match self.master_plan.gestures.get(*gesture_id) {
+                Some(&VersionedGesture(_, ref changed_in)) => match project.based_on.0 {
+                    Some(ref based_on) => {
+                        self.master_plan.in_order(based_on, changed_in) == Some(true)
+                    }
+                    None => true,
+                },
+                None => false,
+            })
+            .collect()
+    }
+
+    pub fn get_conflicts(&mut self, ui: PlanningUIID, project_id: ProjectID, world: &mut World) {
+        let project = match self.projects.get(project_id) {
+            Some(project) => project,
+            None => {
+                error(
+                    LOG_T,
+                    format!("Can't check unknown project {:?} for conflicts", project_id),
+                    self.id,
+                    world,
+                );
+                return;
+            }
+        };
+
+        let touched_gestures = project.touched_gestures();
+        let changed_cells = self.changed_cells_of(project_id);
+
+        let mut shared_gestures = Vec::new();
+        let mut overlapping_cells = Vec::new();
+
+        for (other_id, other_project) in self.projects.pairs() {
+            if *other_id == project_id {
+                continue;
+            }
+
+            let mut shared = other_project
+                .touched_gestures()
+                .intersection(&touched_gestures)
+                .map(|gesture_id| (*other_id, *gesture_id))
+                .collect::<Vec<_>>();
+            shared.sort_by_key(|&(_, gesture_id)| gesture_id.0);
+            shared_gestures.extend(shared);
+
+            let mut overlapping = self
+                .changed_cells_of(*other_id)
+                .intersection(&changed_cells)
+                .map(|grid_coords| (*other_id, *grid_coords))
+                .collect::<Vec<_>>();
+            overlapping.sort_by_key(|&(_, grid_coords)| grid_coords);
+            overlapping_cells.extend(overlapping);
+        }
+
+        let conflicts = ProjectConflicts {
+            outdated_gestures: self.outdated_gestures(project_id).into(),
+            shared_gestures: shared_gestures.into(),
+            overlapping_cells: overlapping_cells.into(),
+        };
+
+        ui.on_project_conflicts(project_id, conflicts, world);
+    }
+
+    // calculated from scratch, so the check doesn't depend on which previews are cached
+    fn changed_cells_of(&self, project_id: ProjectID) -> HashSet<(i32, i32)> {
+        self.projects
+            .get(project_id)
+            .and_then(|project| {
+                project
+                    .apply_to_with_ongoing(&self.master_plan)
+                    .calculate_result()
+                    .ok()
+            })
+            .map(|result: PlanResult| result.grid.changed_cells(&self.master_result.grid))
+            .unwrap_or_default()
+    }
+
+    /// Bases a draft on the latest master plan, so it can be implemented even though
+    /// gestures it changes were changed in the master plan in the meantime.
+    /// Each of those gestures needs a resolution, which decides whether the project
+    /// keeps its own version or drops its changes in favour of the master plan.
+    pub fn rebase(
+        &mut self,
+        project_id: ProjectID,
+        resolutions: &CVec<(GestureID, GestureResolution)>,
+        session: SessionID,
+        world: &mut World,
+    ) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+
+        let resolution_of = |gesture_id: GestureID| {
+            resolutions
+                .iter()
+                .find(|&&(resolved_id, _)| resolved_id == gesture_id)
+                .map(|&(_, resolution)| resolution)
+        };
+
+        let outdated_gestures = self.outdated_gestures(project_id);
+        let n_unresolved = outdated_gestures
+            .iter()
+            .filter(|gesture_id| resolution_of(**gesture_id).is_none())
+            .count();
+
+        if n_unresolved > 0 {
+            warn(
+                LOG_T,
+                format!(
+                    "Can't rebase project {:?}, {} of its gestures that changed in the master \
+                     plan need a resolution first",
+                    project_id, n_unresolved
+                ),
+                self.id,
+                world,
+            );
+            return;
+        }
+
+        self.journal(JournalCommand::Rebase(project_id, resolutions.clone()), session, world);
+
+        let taken_from_master = outdated_gestures
+            .into_iter()
+            .filter(|gesture_id| resolution_of(*gesture_id) == Some(GestureResolution::TakeMaster))
+            .collect::<HashSet<_>>();
+
+        let latest_step = self.master_plan.latest_step_id();
+
+        if let Some(project) = self.projects.get_mut(project_id) {
+            for plan in project
+                .undoable_history
+                .iter_mut()
+                .chain(Some(&mut project.ongoing))
+                .chain(project.redoable_history.iter_mut())
+            {
+                for gesture_id in &taken_from_master {
+                    plan.gestures.remove(*gesture_id);
+                }
+            }
+            project.based_on = COption(Some(latest_step));
+        }
+
+        self.ui_state.invalidate(project_id);
+
+        info(
+            LOG_T,
+            format!(
+                "Rebased project {:?}, took {} gestures from the master plan",
+                project_id,
+                taken_from_master.len()
+            ),
+            self.id,
+            world,
+        );
+    }
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,291 @@
+use descartes::{N, P2, V2};
+use util::random::{seed, Rng};
+use transport::transport_planning::RoadIntent;
+use land_use::zone_planning::{ZoneIntent, LandUse};
+use super::{Plan, Gesture, GestureID, GestureIntent};
+
+// Generators only produce gestures, all geometry (intersections, lanes, lots)
+// is derived from them like for hand-drawn plans.
+
+#[derive(Clone, Serialize, Deserialize)]
+pub enum Generator {
+    Grid(GridSettings),
+    Radial(RadialSettings),
+    Organic(OrganicSettings),
+    CulDeSac(CulDeSacSettings),
+}
+
+impl Generator {
+    pub fn generate(&self) -> Plan {
+        match *self {
+            Generator::Grid(ref settings) => grid(settings),
+            Generator::Radial(ref settings) => radial(settings),
+            Generator::Organic(ref settings) => organic(settings),
+            Generator::CulDeSac(ref settings) => cul_de_sac(settings),
+        }
+    }
+}
+
+fn road(points: Vec<P2>, intent: RoadIntent) -> (GestureID, Gesture) {
+    (
+        GestureID::new(),
+        Gesture::new(points.into(), GestureIntent::Road(intent)),
+    )
+}
+
+fn zone(outline: Vec<P2>, land_use: LandUse) -> (GestureID, Gesture) {
+    (
+        GestureID::new(),
+        Gesture::new(
+            outline.into(),
+            GestureIntent::Zone(ZoneIntent::LandUse(land_use)),
+        ),
+    )
+}
+
+fn rectangle(min: P2, max: P2) -> Vec<P2> {
+    vec![min, P2::new(max.x, min.y), max, P2::new(min.x, max.y)]
+}
+
+fn direction(angle: N) -> V2 {
+    V2::new(angle.cos(), angle.sin())
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GridSettings {
+    pub center: P2,
+    pub n_x: usize,
+    pub n_y: usize,
+    pub spacing: N,
+    pub road: RoadIntent,
+    pub zone: Option<LandUse>,
+}
+
+pub fn grid(settings: &GridSettings) -> Plan {
+    let half_extent = V2::new(
+        settings.n_x.saturating_sub(1) as N * settings.spacing / 2.0,
+        settings.n_y.saturating_sub(1) as N * settings.spacing / 2.0,
+    );
+    let min = settings.center - half_extent;
+    let max = settings.center + half_extent;
+
+    let vertical_roads = (0..settings.n_x).map(|x| {
+        let x = min.x + x as N * settings.spacing;
+        road(vec![P2::new(x, min.y), P2::new(x, max.y)], settings.road)
+    });
+
+    let horizontal_roads = (0..settings.n_y).map(|y| {
+        let y = min.y + y as N * settings.spacing;
+        road(vec![P2::new(min.x, y), P2::new(max.x, y)], settings.road)
+    });
+
+    let zones = settings
+        .zone
+        .map(|land_use| zone(rectangle(min, max), land_use));
+
+    Plan::from_gestures(vertical_roads.chain(horizontal_roads).chain(zones))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RadialSettings {
+    pub center: P2,
+    pub n_spokes: usize,
+    pub n_rings: usize,
+    pub ring_spacing: N,
+    pub spoke_road: RoadIntent,
+    pub ring_road: RoadIntent,
+    pub zone: Option<LandUse>,
+}
+
+const POINTS_PER_RING_SEGMENT: usize = 4;
+
+pub fn radial(settings: &RadialSettings) -> Plan {
+    let n_spokes = settings.n_spokes.max(3);
+    let outer_radius = settings.n_rings.max(1) as N * settings.ring_spacing;
+    let spoke_angle = 2.0 * ::std::f32::consts::PI / n_spokes as N;
+
+    let spokes = (0..n_spokes).map(|spoke| {
+        let direction = direction(spoke as N * spoke_angle);
+        road(
+            vec![
+                settings.center + direction * settings.ring_spacing,
+                settings.center + direction * outer_radius,
+            ],
+            settings.spoke_road,
+        )
+    });
+
+    // rings are split into one gesture per segment between two spokes
+    let ring_segments = (1..=settings.n_rings).flat_map(|ring| {
+        let radius = ring as N * settings.ring_spacing;
+        (0..n_spokes).map(move |spoke| {
+            let points = (0..=POINTS_PER_RING_SEGMENT)
+                .map(|i| {
+                    let angle = (spoke as N + i as N / POINTS_PER_RING_SEGMENT as N) * spoke_angle;
+                    settings.center + direction(angle) * radius
+                })
+                .collect();
+            road(points, settings.ring_road)
+        })
+    });
+
+    let zones = settings.zone.map(|land_use| {
+        let outline = (0..n_spokes * POINTS_PER_RING_SEGMENT)
+            .map(|i| {
+                let angle = i as N * spoke_angle / POINTS_PER_RING_SEGMENT as N;
+                settings.center + direction(angle) * outer_radius
+            })
+            .collect();
+        zone(outline, land_use)
+    });
+
+    Plan::from_gestures(spokes.chain(ring_segments).chain(zones))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OrganicSettings {
+    pub center: P2,
+    pub seed: u64,
+    pub n_iterations: usize,
+    pub segment_length: N,
+    pub branch_probability: f32,
+    pub max_turn: N,
+    pub road: RoadIntent,
+    pub zone: Option<LandUse>,
+}
+
+struct Branch {
+    points: Vec<P2>,
+    heading: N,
+    growing: bool,
+}
+
+pub fn organic(settings: &OrganicSettings) -> Plan {
+    let mut rng = seed(settings.seed);
+    let min_distance = settings.segment_length * 0.7;
+
+    let mut branches: Vec<Branch> = (0..4)
+        .map(|i| Branch {
+            points: vec![settings.center],
+            heading: i as N * ::std::f32::consts::FRAC_PI_2,
+            growing: true,
+        })
+        .collect();
+
+    for _ in 0..settings.n_iterations {
+        let mut new_branches = Vec::new();
+
+        for branch_idx in 0..branches.len() {
+            if !branches[branch_idx].growing {
+                continue;
+            }
+
+            let turn = if settings.max_turn > 0.0 {
+                rng.gen_range(-settings.max_turn, settings.max_turn)
+            } else {
+                0.0
+            };
+            let heading = branches[branch_idx].heading + turn;
+            let tip = *branches[branch_idx]
+                .points
+                .last()
+                .expect("branches always have a start");
+            let next = tip + direction(heading) * settings.segment_length;
+
+            // like in an L-system, growth stops when it would run into existing streets
+            let too_close = branches.iter().any(|branch| {
+                branch
+                    .points
+                    .iter()
+                    .any(|point| (*point - next).norm() < min_distance)
+            });
+
+            if too_close {
+                branches[branch_idx].growing = false;
+                continue;
+            }
+
+            branches[branch_idx].points.push(next);
+            branches[branch_idx].heading = heading;
+
+            if rng.gen::<f32>() < settings.branch_probability {
+                let side = if rng.gen() { 1.0 } else { -1.0 };
+                new_branches.push(Branch {
+                    points: vec![next],
+                    heading: heading + side * ::std::f32::consts::FRAC_PI_2,
+                    growing: true,
+                });
+            }
+        }
+
+        branches.extend(new_branches);
+    }
+
+    let roads = branches
+        .iter()
+        .filter(|branch| branch.points.len() > 1)
+        .map(|branch| road(branch.points.clone(), settings.road))
+        .collect::<Vec<_>>();
+
+    let zones = settings.zone.map(|land_use| {
+        let all_points = branches.iter().flat_map(|branch| branch.points.iter());
+        let (min, max) = all_points.fold(
+            (settings.center, settings.center),
+            |(min, max), point| {
+                (
+                    P2::new(min.x.min(point.x), min.y.min(point.y)),
+                    P2::new(max.x.max(point.x), max.y.max(point.y)),
+                )
+            },
+        );
+        zone(rectangle(min, max), land_use)
+    });
+
+    Plan::from_gestures(roads.into_iter().chain(zones))
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CulDeSacSettings {
+    pub center: P2,
+    pub n_streets: usize,
+    pub street_length: N,
+    pub spacing: N,
+    pub collector_road: RoadIntent,
+    pub street_road: RoadIntent,
+    pub zone: Option<LandUse>,
+}
+
+pub fn cul_de_sac(settings: &CulDeSacSettings) -> Plan {
+    let n_per_side = (settings.n_streets + 1) / 2;
+    let half_length = (n_per_side as N + 1.0) * settings.spacing / 2.0;
+    let start = settings.center - V2::new(half_length, 0.0);
+    let end = settings.center + V2::new(half_length, 0.0);
+
+    let collector = road(vec![start, end], settings.collector_road);
+
+    // dead-end streets alternate sides and curve slightly, like in suburbs
+    let streets = (0..settings.n_streets).map(|i| {
+        let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+        let along = start + V2::new((i / 2 + 1) as N * settings.spacing, 0.0);
+        road(
+            vec![
+                along,
+                along + V2::new(settings.spacing * 0.15, side * settings.street_length * 0.5),
+                along + V2::new(0.0, side * settings.street_length),
+            ],
+            settings.street_road,
+        )
+    });
+
+    let zones = settings.zone.map(|land_use| {
+        zone(
+            rectangle(
+                start - V2::new(0.0, settings.street_length),
+                end + V2::new(0.0, settings.street_length),
+            ),
+            land_use,
+        )
+    });
+
+    Plan::from_gestures(Some(collector).into_iter().chain(streets).chain(zones))
+}
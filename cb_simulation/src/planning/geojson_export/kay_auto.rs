@@ -0,0 +1,32 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl PlanManagerID {
+    pub fn export_geojson(self, path: CString, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_export_geojson(path, session));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_export_geojson(pub CString, pub SessionID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_export_geojson(ref path, session), instance, world| {
+            instance.export_geojson(path, session, world); Fate::Live
+        }, false
+    );
+}
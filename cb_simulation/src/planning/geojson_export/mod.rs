@@ -0,0 +1,256 @@
+use kay::World;
+use compact::CString;
+use descartes::{N, P2, Area, LinePath, RoughEq};
+use serde_json::{self, Map, Value};
+use std::fs::File;
+use std::io::{self, BufWriter};
+
+use transport::transport_planning::{RoadPrototype, LanePrototype, SwitchLanePrototype};
+use land_use::zone_planning::{LotPrototype, LotOccupancy};
+use environment::vegetation::PlantPrototype;
+use super::{PlanManager, PlanManagerID, PlanResult, Prototype, PrototypeKind};
+
+use log::{error, info};
+use access::{ensure_role, Role, SessionID};
+use super::LOG_T;
+
+// Coordinates are the simulation's planar meters, not WGS84,
+// so GIS tools need to be told to treat them as a local projected CRS.
+
+#[derive(Serialize)]
+#[serde(tag = "type", content = "coordinates")]
+pub enum Geometry {
+    Point([N; 2]),
+    LineString(Vec<[N; 2]>),
+    MultiPolygon(Vec<Vec<Vec<[N; 2]>>>),
+}
+
+#[derive(Serialize)]
+pub struct Feature {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    pub geometry: Geometry,
+    pub properties: Map<String, Value>,
+}
+
+#[derive(Serialize)]
+pub struct FeatureCollection {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    pub features: Vec<Feature>,
+}
+
+fn coordinates(point: P2) -> [N; 2] {
+    [point.x, point.y]
+}
+
+fn line_string(path: &LinePath) -> Geometry {
+    Geometry::LineString(path.points.iter().cloned().map(coordinates).collect())
+}
+
+// GeoJSON rings repeat their first point at the end, closed paths usually do already
+fn ring(path: &LinePath) -> Vec<[N; 2]> {
+    let mut ring = path.points.iter().cloned().map(coordinates).collect::<Vec<_>>();
+    if let (Some(&first), Some(&last)) = (path.points.first(), path.points.last()) {
+        if !first.rough_eq_by(last, 0.001) {
+            ring.push(coordinates(first));
+        }
+    }
+    ring
+}
+
+// shoelace formula, positive for counterclockwise rings
+fn signed_area(ring: &[[N; 2]]) -> N {
+    ring.windows(2)
+        .map(|edge| edge[0][0] * edge[1][1] - edge[1][0] * edge[0][1])
+        .sum::<N>()
+        / 2.0
+}
+
+// GeoJSON wants outer rings counterclockwise and holes clockwise
+fn oriented(mut ring: Vec<[N; 2]>, counterclockwise: bool) -> Vec<[N; 2]> {
+    if (signed_area(&ring) > 0.0) != counterclockwise {
+        ring.reverse();
+    }
+    ring
+}
+
+// even-odd ray casting, the rings of an area don't cross, so one of their points is enough
+fn ring_contains(ring: &[[N; 2]], point: [N; 2]) -> bool {
+    ring.windows(2)
+        .filter(|edge| {
+            let (a, b) = (edge[0], edge[1]);
+            (a[1] > point[1]) != (b[1] > point[1])
+                && point[0] < a[0] + (point[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0])
+        })
+        .count()
+        % 2
+        == 1
+}
+
+// Areas are flat lists of rings, GeoJSON polygons are an outer ring followed by its holes.
+// A ring inside an odd number of other rings is a hole of the innermost ring around it.
+fn multi_polygon(area: &Area) -> Geometry {
+    let rings = area
+        .primitives
+        .iter()
+        .map(|primitive| ring(primitive.boundary.path()))
+        .collect::<Vec<_>>();
+
+    let containers = rings
+        .iter()
+        .enumerate()
+        .map(|(i, inner)| {
+            rings
+                .iter()
+                .enumerate()
+                .filter(|&(j, outer)| j != i && ring_contains(outer, inner[0]))
+                .map(|(j, _)| j)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut polygons = Vec::<(usize, Vec<Vec<[N; 2]>>)>::new();
+
+    for (i, ring) in rings.iter().enumerate() {
+        if containers[i].len() % 2 == 0 {
+            polygons.push((i, vec![oriented(ring.clone(), true)]));
+        }
+    }
+
+    for (i, ring) in rings.iter().enumerate() {
+        if containers[i].len() % 2 == 1 {
+            let innermost_outer = containers[i]
+                .iter()
+                .max_by_key(|&&j| containers[j].len())
+                .expect("holes are inside of at least one ring");
+            if let Some(polygon) = polygons
+                .iter_mut()
+                .find(|polygon| polygon.0 == *innermost_outer)
+            {
+                polygon.1.push(oriented(ring.clone(), false));
+            }
+        }
+    }
+
+    Geometry::MultiPolygon(polygons.into_iter().map(|(_, polygon)| polygon).collect())
+}
+
+fn feature(prototype: &Prototype, kind: &str, geometry: Geometry) -> Feature {
+    let mut properties = Map::new();
+    properties.insert("kind".to_owned(), Value::from(kind));
+    properties.insert(
+        "prototype_id".to_owned(),
+        serde_json::to_value(prototype.id).unwrap_or(Value::Null),
+    );
+
+    Feature {
+        kind: "Feature",
+        geometry,
+        properties,
+    }
+}
+
+impl FeatureCollection {
+    pub fn from_plan_result(result: &PlanResult) -> FeatureCollection {
+        let features = result
+            .prototypes
+            .values()
+            .map(|prototype| match prototype.kind {
+                PrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref path, _))) => {
+                    feature(prototype, "lane", line_string(path))
+                }
+                PrototypeKind::Road(RoadPrototype::SwitchLane(SwitchLanePrototype(ref path))) => {
+                    feature(prototype, "switch_lane", line_string(path))
+                }
+                PrototypeKind::Road(RoadPrototype::Intersection(ref intersection)) => {
+                    feature(prototype, "intersection", multi_polygon(&intersection.area))
+                }
+                PrototypeKind::Road(RoadPrototype::PavedArea(ref area)) => {
+                    feature(prototype, "paved_area", multi_polygon(area))
+                }
+                PrototypeKind::Lot(LotPrototype { ref lot, occupancy }) => {
+                    let mut lot_feature = feature(prototype, "lot", multi_polygon(&lot.area));
+                    lot_feature.properties.insert(
+                        "land_uses".to_owned(),
+                        lot.land_uses
+                            .iter()
+                            .map(|land_use| Value::from(land_use.to_string()))
+                            .collect(),
+                    );
+                    lot_feature.properties.insert(
+                        "occupancy".to_owned(),
+                        Value::from(match occupancy {
+                            LotOccupancy::Vacant => "Vacant".to_owned(),
+                            LotOccupancy::Occupied(building_style) => {
+                                format!("{:?}", building_style)
+                            }
+                        }),
+                    );
+                    lot_feature
+                        .properties
+                        .insert("max_height".to_owned(), Value::from(lot.max_height));
+                    lot_feature
+                        .properties
+                        .insert("set_back".to_owned(), Value::from(lot.set_back));
+                    lot_feature
+                }
+                PrototypeKind::Plant(PlantPrototype {
+                    vegetation_type,
+                    position,
+                }) => {
+                    let mut plant_feature =
+                        feature(prototype, "plant", Geometry::Point(coordinates(position)));
+                    plant_feature.properties.insert(
+                        "vegetation_type".to_owned(),
+                        Value::from(format!("{:?}", vegetation_type)),
+                    );
+                    plant_feature
+                }
+            })
+            .collect();
+
+        FeatureCollection {
+            kind: "FeatureCollection",
+            features,
+        }
+    }
+
+    pub fn write(&self, path: &str) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+}
+
+impl PlanManager {
+    pub fn export_geojson(&mut self, path: &CString, session: SessionID, world: &mut World) {
+        // paths are on the server, so only admins may choose them
+        if !ensure_role(session, Role::Admin, "export GeoJSON", self.id, world) {
+            return;
+        }
+
+        let feature_collection = FeatureCollection::from_plan_result(&self.master_result);
+
+        match feature_collection.write(path) {
+            Ok(()) => info(
+                LOG_T,
+                format!(
+                    "Exported {} features to {}",
+                    feature_collection.features.len(),
+                    &**path
+                ),
+                self.id,
+                world,
+            ),
+            Err(err) => error(
+                LOG_T,
+                format!("Couldn't export GeoJSON to {}: {}", &**path, err),
+                self.id,
+                world,
+            ),
+        }
+    }
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
@@ -18,36 +18,44 @@ impl PlanManagerID {
         world.send(self.as_raw(), MSG_PlanManager_get_project_preview_update(ui, project_id, known_result));
     }
     
-    pub fn start_new_gesture(self, project_id: ProjectID, new_gesture_id: GestureID, intent: GestureIntent, start: P2, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_start_new_gesture(project_id, new_gesture_id, intent, start));
+    pub fn start_new_gesture(self, project_id: ProjectID, new_gesture_id: GestureID, intent: GestureIntent, start: P2, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_start_new_gesture(project_id, new_gesture_id, intent, start, session));
     }
     
-    pub fn add_control_point(self, project_id: ProjectID, gesture_id: GestureID, new_point: P2, add_to_end: bool, commit: bool, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_add_control_point(project_id, gesture_id, new_point, add_to_end, commit));
+    pub fn add_control_point(self, project_id: ProjectID, gesture_id: GestureID, new_point: P2, add_to_end: bool, commit: bool, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_add_control_point(project_id, gesture_id, new_point, add_to_end, commit, session));
     }
     
-    pub fn insert_control_point(self, project_id: ProjectID, gesture_id: GestureID, new_point: P2, commit: bool, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_insert_control_point(project_id, gesture_id, new_point, commit));
+    pub fn insert_control_point(self, project_id: ProjectID, gesture_id: GestureID, new_point: P2, commit: bool, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_insert_control_point(project_id, gesture_id, new_point, commit, session));
     }
     
-    pub fn move_control_point(self, project_id: ProjectID, gesture_id: GestureID, point_index: u32, new_position: P2, is_move_finished: bool, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_move_control_point(project_id, gesture_id, point_index, new_position, is_move_finished));
+    pub fn move_control_point(self, project_id: ProjectID, gesture_id: GestureID, point_index: u32, new_position: P2, is_move_finished: bool, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_move_control_point(project_id, gesture_id, point_index, new_position, is_move_finished, session));
     }
     
-    pub fn split_gesture(self, project_id: ProjectID, gesture_id: GestureID, split_at: P2, commit: bool, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_split_gesture(project_id, gesture_id, split_at, commit));
+    pub fn split_gesture(self, project_id: ProjectID, gesture_id: GestureID, split_at: P2, commit: bool, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_split_gesture(project_id, gesture_id, split_at, commit, session));
     }
     
-    pub fn set_intent(self, project_id: ProjectID, gesture_id: GestureID, new_intent: GestureIntent, is_move_finished: bool, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_set_intent(project_id, gesture_id, new_intent, is_move_finished));
+    pub fn split_gesture_as(self, project_id: ProjectID, gesture_id: GestureID, split_at: P2, commit: bool, new_gesture_id: GestureID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_split_gesture_as(project_id, gesture_id, split_at, commit, new_gesture_id, session));
     }
     
-    pub fn undo(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_undo(project_id));
+    pub fn set_intent(self, project_id: ProjectID, gesture_id: GestureID, new_intent: GestureIntent, is_move_finished: bool, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_set_intent(project_id, gesture_id, new_intent, is_move_finished, session));
     }
     
-    pub fn redo(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_redo(project_id));
+    pub fn add_gesture(self, project_id: ProjectID, gesture_id: GestureID, gesture: Gesture, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_add_gesture(project_id, gesture_id, gesture, session));
+    }
+    
+    pub fn undo(self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_undo(project_id, session));
+    }
+    
+    pub fn redo(self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_redo(project_id, session));
     }
 }
 
@@ -56,21 +64,25 @@ struct MSG_PlanManager_get_all_plans(pub PlanningUIID, pub KnownHistoryState, pu
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanManager_get_project_preview_update(pub PlanningUIID, pub ProjectID, pub KnownPlanResultState);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_start_new_gesture(pub ProjectID, pub GestureID, pub GestureIntent, pub P2);
+struct MSG_PlanManager_start_new_gesture(pub ProjectID, pub GestureID, pub GestureIntent, pub P2, pub SessionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_add_control_point(pub ProjectID, pub GestureID, pub P2, pub bool, pub bool);
+struct MSG_PlanManager_add_control_point(pub ProjectID, pub GestureID, pub P2, pub bool, pub bool, pub SessionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_insert_control_point(pub ProjectID, pub GestureID, pub P2, pub bool);
+struct MSG_PlanManager_insert_control_point(pub ProjectID, pub GestureID, pub P2, pub bool, pub SessionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_move_control_point(pub ProjectID, pub GestureID, pub u32, pub P2, pub bool);
+struct MSG_PlanManager_move_control_point(pub ProjectID, pub GestureID, pub u32, pub P2, pub bool, pub SessionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_split_gesture(pub ProjectID, pub GestureID, pub P2, pub bool);
+struct MSG_PlanManager_split_gesture(pub ProjectID, pub GestureID, pub P2, pub bool, pub SessionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_set_intent(pub ProjectID, pub GestureID, pub GestureIntent, pub bool);
+struct MSG_PlanManager_split_gesture_as(pub ProjectID, pub GestureID, pub P2, pub bool, pub GestureID, pub SessionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_undo(pub ProjectID);
+struct MSG_PlanManager_set_intent(pub ProjectID, pub GestureID, pub GestureIntent, pub bool, pub SessionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_redo(pub ProjectID);
+struct MSG_PlanManager_add_gesture(pub ProjectID, pub GestureID, pub Gesture, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_undo(pub ProjectID, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_redo(pub ProjectID, pub SessionID);
 
 
 #[allow(unused_variables)]
@@ -91,50 +103,62 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_start_new_gesture(project_id, new_gesture_id, ref intent, start), instance, world| {
-            instance.start_new_gesture(project_id, new_gesture_id, intent, start, world); Fate::Live
+        |&MSG_PlanManager_start_new_gesture(project_id, new_gesture_id, ref intent, start, session), instance, world| {
+            instance.start_new_gesture(project_id, new_gesture_id, intent, start, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_add_control_point(project_id, gesture_id, new_point, add_to_end, commit, session), instance, world| {
+            instance.add_control_point(project_id, gesture_id, new_point, add_to_end, commit, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_insert_control_point(project_id, gesture_id, new_point, commit, session), instance, world| {
+            instance.insert_control_point(project_id, gesture_id, new_point, commit, session, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_add_control_point(project_id, gesture_id, new_point, add_to_end, commit), instance, world| {
-            instance.add_control_point(project_id, gesture_id, new_point, add_to_end, commit, world); Fate::Live
+        |&MSG_PlanManager_move_control_point(project_id, gesture_id, point_index, new_position, is_move_finished, session), instance, world| {
+            instance.move_control_point(project_id, gesture_id, point_index, new_position, is_move_finished, session, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_insert_control_point(project_id, gesture_id, new_point, commit), instance, world| {
-            instance.insert_control_point(project_id, gesture_id, new_point, commit, world); Fate::Live
+        |&MSG_PlanManager_split_gesture(project_id, gesture_id, split_at, commit, session), instance, world| {
+            instance.split_gesture(project_id, gesture_id, split_at, commit, session, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_move_control_point(project_id, gesture_id, point_index, new_position, is_move_finished), instance, world| {
-            instance.move_control_point(project_id, gesture_id, point_index, new_position, is_move_finished, world); Fate::Live
+        |&MSG_PlanManager_split_gesture_as(project_id, gesture_id, split_at, commit, new_gesture_id, session), instance, world| {
+            instance.split_gesture_as(project_id, gesture_id, split_at, commit, new_gesture_id, session, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_split_gesture(project_id, gesture_id, split_at, commit), instance, world| {
-            instance.split_gesture(project_id, gesture_id, split_at, commit, world); Fate::Live
+        |&MSG_PlanManager_set_intent(project_id, gesture_id, ref new_intent, is_move_finished, session), instance, world| {
+            instance.set_intent(project_id, gesture_id, new_intent, is_move_finished, session, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_set_intent(project_id, gesture_id, ref new_intent, is_move_finished), instance, world| {
-            instance.set_intent(project_id, gesture_id, new_intent, is_move_finished, world); Fate::Live
+        |&MSG_PlanManager_add_gesture(project_id, gesture_id, ref gesture, session), instance, world| {
+            instance.add_gesture(project_id, gesture_id, gesture, session, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_undo(project_id), instance, world| {
-            instance.undo(project_id, world); Fate::Live
+        |&MSG_PlanManager_undo(project_id, session), instance, world| {
+            instance.undo(project_id, session, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_redo(project_id), instance, world| {
-            instance.redo(project_id, world); Fate::Live
+        |&MSG_PlanManager_redo(project_id, session), instance, world| {
+            instance.redo(project_id, session, world); Fate::Live
         }, false
     );
 }
\ No newline at end of file
@@ -3,7 +3,9 @@ use compact::{CHashMap, COption};
 use descartes::{P2, AreaError, LinePath};
 use planning::{ProjectID, PlanHistory, PlanResult, ActionGroups, PlanManager, PlanManagerID, KnownHistoryState, KnownProjectState, ProjectUpdate, GestureID, GestureIntent, Gesture, Plan, KnownPlanResultState};
 use planning::ui::PlanningUIID;
+use planning::journal::JournalCommand;
 use log::error;
+use access::SessionID;
 const LOG_T: &str = "Planning Interaction";
 
 #[derive(Compact, Clone)]
@@ -11,6 +13,7 @@ pub struct PreviewSet {
     history: PlanHistory,
     result: COption<PlanResult>,
     actions: COption<ActionGroups>,
+    construction_cost: COption<f64>,
 }
 
 #[derive(Compact, Clone)]
@@ -81,21 +84,24 @@ impl PlanManager {
         known_result: &KnownPlanResultState,
         world: &mut World,
     ) {
-        let (plan_history, maybe_result, maybe_actions) =
+        let (plan_history, maybe_result, maybe_actions, maybe_cost) =
             self.try_ensure_preview(project_id, world);
 
-        if let (Some(result), Some(actions)) = (maybe_result, maybe_actions) {
+        if let (Some(result), Some(actions), Some(cost)) =
+            (maybe_result, maybe_actions, maybe_cost)
+        {
             ui.on_project_preview_update(
                 project_id,
                 plan_history.clone(),
                 result.update_for(known_result),
                 actions.clone(),
+                *cost,
                 world,
             );
         }
     }
 
-    fn try_ensure_preview(&mut self, project_id: ProjectID, log_in: &mut World) -> (&PlanHistory, Option<&PlanResult>, Option<&ActionGroups>) {
+    fn try_ensure_preview(&mut self, project_id: ProjectID, log_in: &mut World) -> (&PlanHistory, Option<&PlanResult>, Option<&ActionGroups>, Option<&f64>) {
         if !self.ui_state.previews.contains_key(project_id) {
             let preview_history = self
                     .projects
@@ -119,10 +125,17 @@ impl PlanManager {
                 self.master_result.actions_to(preview_plan_result).0
             );
 
+            let maybe_preview_cost = maybe_preview_result.as_ref().and_then(|preview_plan_result|
+                maybe_preview_actions.as_ref().map(|preview_actions|
+                    preview_actions.construction_cost(&self.master_result, preview_plan_result)
+                )
+            );
+
             self.ui_state.previews.insert(project_id, PreviewSet {
                 history: preview_history,
                 result: COption(maybe_preview_result),
-                actions: COption(maybe_preview_actions)
+                actions: COption(maybe_preview_actions),
+                construction_cost: COption(maybe_preview_cost),
             });
         }
 
@@ -132,6 +145,7 @@ impl PlanManager {
             &preview_set.history,
             preview_set.result.as_ref(),
             preview_set.actions.as_ref(),
+            preview_set.construction_cost.as_ref(),
         )
     }
 
@@ -141,8 +155,17 @@ impl PlanManager {
         new_gesture_id: GestureID,
         intent: &GestureIntent,
         start: P2,
-        _: &mut World,
+        session: SessionID,
+        world: &mut World,
     ) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(
+            JournalCommand::StartNewGesture(project_id, new_gesture_id, intent.clone(), start),
+            session,
+            world,
+        );
         let new_gesture = Gesture::new(vec![start].into(), intent.clone());
 
         let new_step = Plan::from_gestures(Some((new_gesture_id, new_gesture)));
@@ -163,8 +186,17 @@ impl PlanManager {
         new_point: P2,
         add_to_end: bool,
         commit: bool,
-        _: &mut World,
+        session: SessionID,
+        world: &mut World,
     ) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(
+            JournalCommand::AddControlPoint(project_id, gesture_id, new_point, add_to_end, commit),
+            session,
+            world,
+        );
         let new_step = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -209,8 +241,17 @@ impl PlanManager {
         gesture_id: GestureID,
         new_point: P2,
         commit: bool,
-        _: &mut World,
+        session: SessionID,
+        world: &mut World,
     ) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(
+            JournalCommand::InsertControlPoint(project_id, gesture_id, new_point, commit),
+            session,
+            world,
+        );
         let new_step = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -257,8 +298,23 @@ impl PlanManager {
         point_index: u32,
         new_position: P2,
         is_move_finished: bool,
-        _: &mut World,
+        session: SessionID,
+        world: &mut World,
     ) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(
+            JournalCommand::MoveControlPoint(
+                project_id,
+                gesture_id,
+                point_index,
+                new_position,
+                is_move_finished,
+            ),
+            session,
+            world,
+        );
         let current_change = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -297,8 +353,41 @@ impl PlanManager {
         gesture_id: GestureID,
         split_at: P2,
         commit: bool,
-        _: &mut World,
+        session: SessionID,
+        world: &mut World,
+    ) {
+        let new_gesture_id = GestureID::new();
+        self.split_gesture_as(
+            project_id,
+            gesture_id,
+            split_at,
+            commit,
+            new_gesture_id,
+            session,
+            world,
+        );
+    }
+
+    // the ID of the split off second half is explicit, so journal replays reproduce it
+    pub fn split_gesture_as(
+        &mut self,
+        project_id: ProjectID,
+        gesture_id: GestureID,
+        split_at: P2,
+        commit: bool,
+        new_gesture_id: GestureID,
+        session: SessionID,
+        world: &mut World,
     ) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(
+            JournalCommand::SplitGesture(project_id, gesture_id, split_at, commit, new_gesture_id),
+            session,
+            world,
+        );
+
         let maybe_new_step = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -336,7 +425,7 @@ impl PlanManager {
 
                 Some(Plan::from_gestures(vec![
                     (gesture_id, first_half),
-                    (GestureID::new(), second_half),
+                    (new_gesture_id, second_half),
                 ]))
             } else {
                 None
@@ -363,8 +452,17 @@ impl PlanManager {
         gesture_id: GestureID,
         new_intent: &GestureIntent,
         is_move_finished: bool,
-        _: &mut World,
+        session: SessionID,
+        world: &mut World,
     ) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(
+            JournalCommand::SetIntent(project_id, gesture_id, new_intent.clone(), is_move_finished),
+            session,
+            world,
+        );
         let current_change = {
             let current_gesture = self.get_current_version_of(gesture_id, project_id);
 
@@ -390,12 +488,54 @@ impl PlanManager {
         }
     }
 
-    pub fn undo(&mut self, project_id: ProjectID, _: &mut World) {
+    /// Adds a whole gesture to a project at once, as one undoable step
+    pub fn add_gesture(
+        &mut self,
+        project_id: ProjectID,
+        gesture_id: GestureID,
+        gesture: &Gesture,
+        session: SessionID,
+        world: &mut World,
+    ) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(
+            JournalCommand::AddGesture(project_id, gesture_id, gesture.clone()),
+            session,
+            world,
+        );
+
+        if let Some(project) = self.projects.get_mut(project_id) {
+            project.set_ongoing_step(Plan::from_gestures(Some((gesture_id, gesture.clone()))));
+            project.start_new_step();
+        } else {
+            error(
+                LOG_T,
+                format!("Can't add gesture to unknown project {:?}", project_id),
+                self.id,
+                world,
+            );
+            return;
+        }
+
+        self.ui_state.invalidate(project_id);
+    }
+
+    pub fn undo(&mut self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(JournalCommand::Undo(project_id), session, world);
         self.projects.get_mut(project_id).unwrap().undo();
         self.ui_state.invalidate(project_id);
     }
 
-    pub fn redo(&mut self, project_id: ProjectID, _: &mut World) {
+    pub fn redo(&mut self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(JournalCommand::Redo(project_id), session, world);
         self.projects.get_mut(project_id).unwrap().redo();
         self.ui_state.invalidate(project_id);
     }
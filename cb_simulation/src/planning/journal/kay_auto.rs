@@ -0,0 +1,139 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for PlanJournal {
+    type ID = PlanJournalID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct PlanJournalID {
+    _raw_id: RawID
+}
+
+impl TypedID for PlanJournalID {
+    type Target = PlanJournal;
+
+    fn from_raw(id: RawID) -> Self {
+        PlanJournalID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl PlanJournalID {
+    pub fn spawn(path: CString, world: &mut World) -> Self {
+        let id = PlanJournalID::from_raw(world.allocate_instance_id::<PlanJournal>());
+        let swarm = world.local_broadcast::<PlanJournal>();
+        world.send(swarm, MSG_PlanJournal_spawn(id, path));
+        id
+    }
+    
+    pub fn record(self, entry: JournalEntry, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanJournal_record(entry));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanJournal_spawn(pub PlanJournalID, pub CString);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanJournal_record(pub JournalEntry);
+
+impl Actor for JournalReplayer {
+    type ID = JournalReplayerID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct JournalReplayerID {
+    _raw_id: RawID
+}
+
+impl TypedID for JournalReplayerID {
+    type Target = JournalReplayer;
+
+    fn from_raw(id: RawID) -> Self {
+        JournalReplayerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl JournalReplayerID {
+    pub fn spawn(time: TimeID, plan_manager: PlanManagerID, entries: CVec < JournalEntry >, world: &mut World) -> Self {
+        let id = JournalReplayerID::from_raw(world.allocate_instance_id::<JournalReplayer>());
+        let swarm = world.local_broadcast::<JournalReplayer>();
+        world.send(swarm, MSG_JournalReplayer_spawn(id, time, plan_manager, entries));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_JournalReplayer_spawn(pub JournalReplayerID, pub TimeID, pub PlanManagerID, pub CVec < JournalEntry >);
+
+impl Into<SleeperID> for JournalReplayerID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl PlanManagerID {
+    pub fn start_journal(self, journal: PlanJournalID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_start_journal(journal, session));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_start_journal(pub PlanJournalID, pub SessionID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    system.add_spawner::<PlanJournal, _, _>(
+        |&MSG_PlanJournal_spawn(id, ref path), world| {
+            PlanJournal::spawn(id, path, world)
+        }, false
+    );
+    
+    system.add_handler::<PlanJournal, _, _>(
+        |&MSG_PlanJournal_record(ref entry), instance, world| {
+            instance.record(entry, world); Fate::Live
+        }, false
+    );
+    SleeperID::register_implementor::<JournalReplayer>(system);
+    system.add_spawner::<JournalReplayer, _, _>(
+        |&MSG_JournalReplayer_spawn(id, time, plan_manager, ref entries), world| {
+            JournalReplayer::spawn(id, time, plan_manager, entries, world)
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_start_journal(journal, session), instance, world| {
+            instance.start_journal(journal, session, world); Fate::Live
+        }, false
+    );
+}
@@ -0,0 +1,316 @@
+use kay::{ActorSystem, World, TypedID};
+use compact::{CVec, COption, CString};
+use descartes::P2;
+use serde_json;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, LineWriter, Write};
+use std::sync::Mutex;
+
+use time::{TimeID, Instant, ScheduleID, Sleeper, SleeperID};
+use super::{PlanManager, PlanManagerID, Project, ProjectID, Gesture, GestureID, GestureIntent};
+use super::conflicts::GestureResolution;
+
+use log::error;
+use access::{server_session, open_session, role_of, ensure_role, Role, SessionID, PlayerID};
+const LOG_T: &str = "Planning Journal";
+
+/// Every change to the plans of the `PlanManager`, recorded so a session can be replayed exactly
+#[derive(Compact, Clone, Serialize, Deserialize, Debug)]
+pub enum JournalCommand {
+    StartNewProject(ProjectID),
+    AddProject(ProjectID, Project),
+    StartNewGesture(ProjectID, GestureID, GestureIntent, P2),
+    AddControlPoint(ProjectID, GestureID, P2, bool, bool),
+    InsertControlPoint(ProjectID, GestureID, P2, bool),
+    MoveControlPoint(ProjectID, GestureID, u32, P2, bool),
+    // the last ID is the one the split off second half got
+    SplitGesture(ProjectID, GestureID, P2, bool, GestureID),
+    SetIntent(ProjectID, GestureID, GestureIntent, bool),
+    AddGesture(ProjectID, GestureID, Gesture),
+    Undo(ProjectID),
+    Redo(ProjectID),
+    Implement(ProjectID),
+    Rebase(ProjectID, CVec<(GestureID, GestureResolution)>),
+    SetProjectInfo(ProjectID, CString, CString),
+    Propose(ProjectID),
+    Withdraw(ProjectID),
+    // whether the project was approved
+    Review(ProjectID, bool),
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize, Debug)]
+pub struct JournalEntry {
+    pub instant: Instant,
+    /// Who made the change, replays make it again with the same role and as the same player
+    pub role: Role,
+    pub player: COption<PlayerID>,
+    pub command: JournalCommand,
+}
+
+/// Reads a journal, which consists of one JSON-encoded `JournalEntry` per line
+pub fn read_journal(path: &str) -> io::Result<Vec<JournalEntry>> {
+    BufReader::new(File::open(path)?)
+        .lines()
+        .filter(|line| line.as_ref().map(|line| !line.trim().is_empty()).unwrap_or(true))
+        .map(|line| serde_json::from_str(&line?).map_err(io::Error::from))
+        .collect()
+}
+
+// Files can't live inside of actors, so the journal is written through this file,
+// which stays open for as long as the server runs
+lazy_static! {
+    static ref JOURNAL_FILE: Mutex<Option<LineWriter<File>>> = Mutex::new(None);
+}
+
+#[derive(Compact, Clone)]
+pub struct PlanJournal {
+    id: PlanJournalID,
+    path: CString,
+    failed: bool,
+}
+
+impl PlanJournal {
+    pub fn spawn(id: PlanJournalID, path: &CString, _: &mut World) -> PlanJournal {
+        PlanJournal {
+            id,
+            path: path.clone(),
+            failed: false,
+        }
+    }
+
+    fn append(&self, entry: &JournalEntry) -> io::Result<()> {
+        let line = serde_json::to_string(entry).map_err(io::Error::from)?;
+        match *JOURNAL_FILE
+            .lock()
+            .expect("journal file lock shouldn't be poisoned")
+        {
+            Some(ref mut file) => writeln!(file, "{}", line),
+            None => Err(io::Error::new(io::ErrorKind::NotFound, "the journal isn't open")),
+        }
+    }
+
+    pub fn record(&mut self, entry: &JournalEntry, world: &mut World) {
+        if !self.failed {
+            if let Err(err) = self.append(entry) {
+                error(
+                    LOG_T,
+                    format!("Couldn't write to journal {}: {}", &*self.path, err),
+                    self.id,
+                    world,
+                );
+                // don't flood the log with the same error for every gesture
+                self.failed = true;
+            }
+        }
+    }
+}
+
+impl PlanManager {
+    pub fn start_journal(&mut self, journal: PlanJournalID, session: SessionID, world: &mut World) {
+        // the journal is written to a path on the server
+        if !ensure_role(session, Role::Admin, "start a journal", self.id, world) {
+            return;
+        }
+        self.journal = Some(journal);
+    }
+}
+
+/// Sends a recorded command to `plan_manager` as the message it was recorded from
+fn replay_command(
+    plan_manager: PlanManagerID,
+    command: JournalCommand,
+    session: SessionID,
+    world: &mut World,
+) {
+    match command {
+        JournalCommand::StartNewProject(project_id) => {
+            plan_manager.start_new_project(project_id, session, world)
+        }
+        JournalCommand::AddProject(project_id, project) => {
+            plan_manager.add_project(project_id, project, session, world)
+        }
+        JournalCommand::StartNewGesture(project_id, gesture_id, intent, start) => {
+            plan_manager.start_new_gesture(project_id, gesture_id, intent, start, session, world)
+        }
+        JournalCommand::AddControlPoint(project_id, gesture_id, point, add_to_end, commit) => {
+            plan_manager.add_control_point(
+                project_id,
+                gesture_id,
+                point,
+                add_to_end,
+                commit,
+                session,
+                world,
+            )
+        }
+        JournalCommand::InsertControlPoint(project_id, gesture_id, point, commit) => {
+            plan_manager.insert_control_point(project_id, gesture_id, point, commit, session, world)
+        }
+        JournalCommand::MoveControlPoint(project_id, gesture_id, index, position, finished) => {
+            plan_manager.move_control_point(
+                project_id,
+                gesture_id,
+                index,
+                position,
+                finished,
+                session,
+                world,
+            )
+        }
+        JournalCommand::SplitGesture(project_id, gesture_id, split_at, commit, new_id) => {
+            plan_manager.split_gesture_as(
+                project_id,
+                gesture_id,
+                split_at,
+                commit,
+                new_id,
+                session,
+                world,
+            )
+        }
+        JournalCommand::SetIntent(project_id, gesture_id, intent, finished) => {
+            plan_manager.set_intent(project_id, gesture_id, intent, finished, session, world)
+        }
+        JournalCommand::AddGesture(project_id, gesture_id, gesture) => {
+            plan_manager.add_gesture(project_id, gesture_id, gesture, session, world)
+        }
+        JournalCommand::Undo(project_id) => plan_manager.undo(project_id, session, world),
+        JournalCommand::Redo(project_id) => plan_manager.redo(project_id, session, world),
+        JournalCommand::Implement(project_id) => plan_manager.implement(project_id, session, world),
+        JournalCommand::Rebase(project_id, resolutions) => {
+            plan_manager.rebase(project_id, resolutions, session, world)
+        }
+        JournalCommand::SetProjectInfo(project_id, title, description) => {
+            plan_manager.set_project_info(project_id, title, description, session, world)
+        }
+        JournalCommand::Propose(project_id) => plan_manager.propose(project_id, session, world),
+        JournalCommand::Withdraw(project_id) => plan_manager.withdraw(project_id, session, world),
+        JournalCommand::Review(project_id, true) => {
+            plan_manager.approve(project_id, session, world)
+        }
+        JournalCommand::Review(project_id, false) => {
+            plan_manager.reject(project_id, session, world)
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct JournalReplayer {
+    id: JournalReplayerID,
+    plan_manager: PlanManagerID,
+    // sorted latest first, so due entries can be popped off the end
+    entries: CVec<JournalEntry>,
+    // sessions opened to act as the recorded players
+    sessions: CVec<(Role, PlayerID, SessionID)>,
+}
+
+impl JournalReplayer {
+    pub fn spawn(
+        id: JournalReplayerID,
+        time: TimeID,
+        plan_manager: PlanManagerID,
+        entries: &CVec<JournalEntry>,
+        world: &mut World,
+    ) -> JournalReplayer {
+        let mut entries = entries.clone();
+        // stable, so entries of the same instant keep their recorded order
+        entries.sort_by_key(|entry| entry.instant);
+        entries.reverse();
+
+        let mut scheduled_at = None;
+        for entry in entries.iter() {
+            if scheduled_at != Some(entry.instant) {
+                time.wake_up_at(entry.instant, id.into(), ScheduleID::new(), world);
+                scheduled_at = Some(entry.instant);
+            }
+        }
+
+        JournalReplayer {
+            id,
+            plan_manager,
+            entries,
+            sessions: CVec::new(),
+        }
+    }
+
+    // A session with the recorded role, acting as the recorded player.
+    // Changes without a player were made by the server itself or through the control API.
+    fn session_for(&mut self, role: Role, player: Option<PlayerID>) -> SessionID {
+        let player = match player {
+            Some(player) => player,
+            None => return server_session(),
+        };
+
+        let known_session = self
+            .sessions
+            .iter()
+            .find(|&&(known_role, known_player, _)| known_role == role && known_player == player)
+            .map(|&(_, _, session)| session);
+
+        match known_session {
+            // sessions expire when they aren't used for a long time
+            Some(session) if role_of(session).is_some() => session,
+            _ => {
+                let session = open_session(role, player);
+                self.sessions.retain(|&(known_role, known_player, _)| {
+                    known_role != role || known_player != player
+                });
+                self.sessions.push((role, player, session));
+                session
+            }
+        }
+    }
+}
+
+impl Sleeper for JournalReplayer {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        while self
+            .entries
+            .last()
+            .map(|entry| entry.instant <= current_instant)
+            .unwrap_or(false)
+        {
+            let entry = self
+                .entries
+                .pop()
+                .expect("just checked that there are due entries");
+            let session = self.session_for(entry.role, entry.player.0);
+            replay_command(self.plan_manager, entry.command, session, world);
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<PlanJournal>();
+    system.register::<JournalReplayer>();
+    auto_setup(system);
+}
+
+/// Starts recording all plan mutations to the journal at `path`
+pub fn start(
+    path: &str,
+    plan_manager: PlanManagerID,
+    world: &mut World,
+) -> io::Result<PlanJournalID> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    *JOURNAL_FILE
+        .lock()
+        .expect("journal file lock shouldn't be poisoned") = Some(LineWriter::new(file));
+    let journal = PlanJournalID::spawn(path.to_owned().into(), world);
+    plan_manager.start_journal(journal, server_session(), world);
+    Ok(journal)
+}
+
+/// Replays a journal against `plan_manager`, each entry at its recorded instant
+pub fn replay(
+    path: &str,
+    time: TimeID,
+    plan_manager: PlanManagerID,
+    world: &mut World,
+) -> io::Result<JournalReplayerID> {
+    let entries = read_journal(path)?;
+    Ok(JournalReplayerID::spawn(time, plan_manager, entries.into(), world))
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
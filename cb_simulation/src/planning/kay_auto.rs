@@ -43,27 +43,45 @@ impl PlanManagerID {
         id
     }
     
-    pub fn start_new_project(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_start_new_project(project_id));
+    pub fn start_new_project(self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_start_new_project(project_id, session));
     }
     
-    pub fn implement(self, project_id: ProjectID, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_implement(project_id));
+    pub fn add_project(self, project_id: ProjectID, project: Project, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_add_project(project_id, project, session));
     }
     
-    pub fn implement_artificial_project(self, project: Project, based_on: CVec < PrototypeID >, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanManager_implement_artificial_project(project, based_on));
+    pub fn implement(self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_implement(project_id, session));
+    }
+    
+    pub fn on_construction_paid(self, project_id: ProjectID, paid: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_on_construction_paid(project_id, paid));
+    }
+    
+    pub fn report_infrastructure_value(self, treasury: TreasuryID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_report_infrastructure_value(treasury));
+    }
+    
+    pub fn implement_artificial_project(self, project: Project, based_on: CVec < PrototypeID >, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_implement_artificial_project(project, based_on, session));
     }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanManager_spawn(pub PlanManagerID, );
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_start_new_project(pub ProjectID);
+struct MSG_PlanManager_start_new_project(pub ProjectID, pub SessionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_implement(pub ProjectID);
+struct MSG_PlanManager_add_project(pub ProjectID, pub Project, pub SessionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanManager_implement_artificial_project(pub Project, pub CVec < PrototypeID >);
+struct MSG_PlanManager_implement(pub ProjectID, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_on_construction_paid(pub ProjectID, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_report_infrastructure_value(pub TreasuryID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_implement_artificial_project(pub Project, pub CVec < PrototypeID >, pub SessionID);
 
 
 #[allow(unused_variables)]
@@ -78,20 +96,38 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_start_new_project(project_id), instance, world| {
-            instance.start_new_project(project_id, world); Fate::Live
+        |&MSG_PlanManager_start_new_project(project_id, session), instance, world| {
+            instance.start_new_project(project_id, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_add_project(project_id, ref project, session), instance, world| {
+            instance.add_project(project_id, project, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_implement(project_id, session), instance, world| {
+            instance.implement(project_id, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_on_construction_paid(project_id, paid), instance, world| {
+            instance.on_construction_paid(project_id, paid, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_implement(project_id), instance, world| {
-            instance.implement(project_id, world); Fate::Live
+        |&MSG_PlanManager_report_infrastructure_value(treasury), instance, world| {
+            instance.report_infrastructure_value(treasury, world); Fate::Live
         }, false
     );
     
     system.add_handler::<PlanManager, _, _>(
-        |&MSG_PlanManager_implement_artificial_project(ref project, ref based_on), instance, world| {
-            instance.implement_artificial_project(project, based_on, world); Fate::Live
+        |&MSG_PlanManager_implement_artificial_project(ref project, ref based_on, session), instance, world| {
+            instance.implement_artificial_project(project, based_on, session, world); Fate::Live
         }, false
     );
 }
\ No newline at end of file
@@ -1,21 +1,32 @@
 #![allow(clippy::new_without_default_derive)]
 #![allow(clippy::new_without_default)]
 use kay::{World, ActorSystem, TypedID};
-use compact::{CVec, COption, CHashMap};
+use compact::{CVec, COption, CHashMap, CString};
 use descartes::{N, P2, AreaError};
 use util::random::{seed, RngCore, Uuid, uuid};
 use std::hash::Hash;
+use std::collections::HashSet;
 
 use transport::transport_planning::{RoadIntent, RoadPrototype};
 use land_use::zone_planning::{ZoneIntent, BuildingIntent, LotPrototype};
 use environment::vegetation::{PlantIntent, PlantPrototype};
 use construction::ConstructionID;
+use economy::treasury::TreasuryID;
 
-use log::{error, info};
+use log::{error, info, warn};
+use access::{SessionID, PlayerID, Role, ensure_role, role_of, player_of, server_session};
+use tuning::required_project_approvals;
 const LOG_T: &str = "Planning";
 
 pub mod interaction;
 pub mod ui;
+pub mod plan_files;
+pub mod generators;
+pub mod osm_import;
+pub mod geojson_export;
+pub mod journal;
+pub mod review;
+pub mod conflicts;
 
 // idea for improvement:
 // - everything (Gestures, Prototypes) immutable (helps caching)
@@ -246,11 +257,51 @@ impl PlanHistoryUpdate {
     }
 }
 
+/// Where a project is in its review, only drafts can be edited
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum ProjectStatus {
+    Draft,
+    Proposed,
+    Approved,
+    Implemented,
+    Rejected,
+}
+
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct ProjectInfo {
+    /// Projects without an owner, like imported ones, can be managed by every planner
+    pub owner: COption<PlayerID>,
+    pub title: CString,
+    pub description: CString,
+    pub status: ProjectStatus,
+    pub approvals: CVec<PlayerID>,
+    pub rejections: CVec<PlayerID>,
+    // lets clients notice changes to the info, which aren't steps of the history
+    revision: u32,
+}
+
+impl ProjectInfo {
+    pub fn new(owner: Option<PlayerID>) -> ProjectInfo {
+        ProjectInfo {
+            owner: COption(owner),
+            title: CString::new(),
+            description: CString::new(),
+            status: ProjectStatus::Draft,
+            approvals: CVec::new(),
+            rejections: CVec::new(),
+            revision: 0,
+        }
+    }
+}
+
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
 pub struct Project {
     undoable_history: CVec<Plan>,
     ongoing: Plan,
     redoable_history: CVec<Plan>,
+    info: ProjectInfo,
+    // the latest step of the master plan when the project was started or last rebased
+    based_on: COption<StepID>,
 }
 
 impl Project {
@@ -259,6 +310,8 @@ impl Project {
             undoable_history: CVec::new(),
             ongoing: Plan::new(),
             redoable_history: CVec::new(),
+            info: ProjectInfo::new(None),
+            based_on: COption(None),
         }
     }
 
@@ -267,9 +320,20 @@ impl Project {
             undoable_history: vec![plan].into(),
             ongoing: Plan::new(),
             redoable_history: CVec::new(),
+            info: ProjectInfo::new(None),
+            based_on: COption(None),
         }
     }
 
+    pub fn info(&self) -> &ProjectInfo {
+        &self.info
+    }
+
+    pub fn change_info<F: FnOnce(&mut ProjectInfo)>(&mut self, change: F) {
+        change(&mut self.info);
+        self.info.revision += 1;
+    }
+
     pub fn start_new_step(&mut self) {
         self.undoable_history.push(self.ongoing.clone());
         self.ongoing = Plan::new();
@@ -298,6 +362,15 @@ impl Project {
         &self.undoable_history
     }
 
+    /// All gestures that the current history or the ongoing step of this project change
+    pub fn touched_gestures(&self) -> HashSet<GestureID> {
+        self.undoable_history
+            .iter()
+            .chain(Some(&self.ongoing))
+            .flat_map(|plan| plan.gestures.keys().cloned())
+            .collect()
+    }
+
     fn apply_to(&self, base: &PlanHistory) -> PlanHistory {
         base.and_then(&self.undoable_history)
     }
@@ -311,6 +384,7 @@ impl Project {
             known_last_undoable: COption(self.undoable_history.last().map(|plan| plan.step_id)),
             known_ongoing: COption(Some(self.ongoing.step_id)),
             known_first_redoable: COption(self.redoable_history.first().map(|plan| plan.step_id)),
+            known_info_revision: COption(Some(self.info.revision)),
         }
     }
 
@@ -320,6 +394,8 @@ impl Project {
             && known_state.known_first_redoable.0.is_none()
         {
             ProjectUpdate::ChangedCompletely(self.clone())
+        } else if known_state.known_info_revision.0 != Some(self.info.revision) {
+            ProjectUpdate::ChangedCompletely(self.clone())
         } else if self.undoable_history.last().map(|plan| plan.step_id)
             == known_state.known_last_undoable.0
             && self.redoable_history.first().map(|plan| plan.step_id)
@@ -352,6 +428,7 @@ pub struct KnownProjectState {
     known_last_undoable: COption<StepID>,
     known_ongoing: COption<StepID>,
     known_first_redoable: COption<StepID>,
+    known_info_revision: COption<u32>,
 }
 
 impl Default for KnownProjectState {
@@ -360,6 +437,7 @@ impl Default for KnownProjectState {
             known_last_undoable: COption(None),
             known_ongoing: COption(None),
             known_first_redoable: COption(None),
+            known_info_revision: COption(None),
         }
     }
 }
@@ -438,6 +516,23 @@ impl PrototypesSpatialGrid {
         grid_cell.content_hash = PrototypeID::from_influences(&grid_cell.members);
     }
 
+    /// Coordinates of all cells whose members differ between `self` and `other`
+    pub fn changed_cells(&self, other: &Self) -> HashSet<(i32, i32)> {
+        let changed_in_self = self.cells.pairs().filter(|&(grid_coords, cell)| {
+            other.cells.get(*grid_coords).map(|other_cell| other_cell.content_hash)
+                != Some(cell.content_hash)
+        });
+        let only_in_other = other
+            .cells
+            .pairs()
+            .filter(|&(grid_coords, _)| !self.cells.contains_key(*grid_coords));
+
+        changed_in_self
+            .chain(only_in_other)
+            .map(|(grid_coords, _)| *grid_coords)
+            .collect()
+    }
+
     pub fn difference(&self, other: &Self) -> (Vec<PrototypeID>, Vec<PrototypeID>) {
         let mut only_in_self = Vec::new();
         let mut only_in_other = Vec::new();
@@ -792,6 +887,7 @@ impl PlanHistory {
 }
 
 use self::interaction::PlanManagerUIState;
+use self::journal::{PlanJournalID, JournalCommand, JournalEntry};
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
 pub struct ProjectID(pub Uuid);
@@ -803,18 +899,34 @@ impl ProjectID {
 }
 
 // #[derive(Compact, Clone)]
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct PlanManager {
     id: PlanManagerID,
     master_plan: PlanHistory,
     master_result: PlanResult,
     projects: CHashMap<ProjectID, Project>,
     implemented_projects: CHashMap<ProjectID, Project>,
+    // only caches previews, gets rebuilt on demand
+    #[serde(skip, default = "PlanManagerUIState::new")]
     ui_state: PlanManagerUIState,
+    // journals are tied to a server session, not to the city
+    #[serde(skip)]
+    journal: Option<PlanJournalID>,
+    // projects that are implemented as soon as the treasury paid what they cost,
+    // they can't be changed until then
+    #[serde(skip, default = "CVec::new")]
+    awaiting_funds: CVec<(ProjectID, f64)>,
 }
 
 mod compact_workaround;
 
+// owners manage their own projects, admins and unowned projects are a shared responsibility
+fn may_manage(info: &ProjectInfo, session: SessionID) -> bool {
+    info.owner.0.is_none()
+        || info.owner.0 == player_of(session)
+        || role_of(session) == Some(Role::Admin)
+}
+
 impl PlanManager {
     pub fn spawn(id: PlanManagerID, _: &mut World) -> PlanManager {
         PlanManager {
@@ -824,6 +936,110 @@ impl PlanManager {
             projects: CHashMap::new(),
             implemented_projects: CHashMap::new(),
             ui_state: PlanManagerUIState::new(),
+            journal: None,
+            awaiting_funds: CVec::new(),
+        }
+    }
+
+    fn may_plan(&self, session: SessionID, world: &mut World) -> bool {
+        ensure_role(session, Role::Planner, "change plans", self.id, world)
+    }
+
+    // only drafts can be changed, and only by whoever manages them
+    fn may_edit(&self, project_id: ProjectID, session: SessionID, world: &mut World) -> bool {
+        if !self.may_plan(session, world) {
+            return false;
+        }
+
+        let refusal = match self.projects.get(project_id) {
+            None => format!("Can't change unknown project {:?}", project_id),
+            Some(project) if project.info.status != ProjectStatus::Draft => format!(
+                "Project {:?} is {:?}, only drafts can be changed",
+                project_id, project.info.status
+            ),
+            Some(project) if !may_manage(&project.info, session) => {
+                format!("Only the owner or an admin may change project {:?}", project_id)
+            }
+            Some(_) if self.is_awaiting_funds(project_id) => format!(
+                "Project {:?} is waiting to be paid for and can't be changed",
+                project_id
+            ),
+            Some(_) => return true,
+        };
+
+        warn(LOG_T, refusal, self.id, world);
+        false
+    }
+
+    fn may_implement(&self, project_id: ProjectID, session: SessionID, world: &mut World) -> bool {
+        let info = match self.projects.get(project_id) {
+            Some(project) => &project.info,
+            // implementing reports unknown projects itself
+            None => return true,
+        };
+
+        let refusal = if !may_manage(info, session) {
+            format!("Only the owner or an admin may implement project {:?}", project_id)
+        } else if let Some(refusal) = self.implementation_refusal(project_id) {
+            refusal
+        } else {
+            return true;
+        };
+
+        warn(LOG_T, refusal, self.id, world);
+        false
+    }
+
+    // why a project can't become part of the master plan as it is right now, if it can't
+    fn implementation_refusal(&self, project_id: ProjectID) -> Option<String> {
+        let info = match self.projects.get(project_id) {
+            Some(project) => &project.info,
+            None => return Some(format!("Can't implement unknown project {:?}", project_id)),
+        };
+
+        let approved = match info.status {
+            ProjectStatus::Approved => true,
+            ProjectStatus::Draft | ProjectStatus::Proposed => required_project_approvals() == 0,
+            ProjectStatus::Implemented | ProjectStatus::Rejected => false,
+        };
+
+        let n_outdated_gestures = self.outdated_gestures(project_id).len();
+
+        if !approved {
+            Some(format!(
+                "Project {:?} is {:?}, it needs {} approvals to be implemented",
+                project_id,
+                info.status,
+                required_project_approvals()
+            ))
+        } else if n_outdated_gestures > 0 {
+            Some(format!(
+                "Project {:?} changes {} gestures that changed in the master plan since, \
+                 it needs to be rebased to be implemented",
+                project_id, n_outdated_gestures
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn is_awaiting_funds(&self, project_id: ProjectID) -> bool {
+        self.awaiting_funds
+            .iter()
+            .any(|&(awaiting_id, _)| awaiting_id == project_id)
+    }
+
+    fn journal(&self, command: JournalCommand, session: SessionID, world: &mut World) {
+        if let Some(journal) = self.journal {
+            journal.record(
+                JournalEntry {
+                    instant: ::time::latest_instant(),
+                    role: role_of(session).unwrap_or(Role::Spectator),
+                    player: COption(player_of(session)),
+                    command,
+                },
+                world,
+            );
         }
     }
 
@@ -847,26 +1063,190 @@ impl PlanManager {
             .expect("Expected gesture (that point should be added to) to exist!")
     }
 
-    pub fn start_new_project(&mut self, project_id: ProjectID, _: &mut World) {
-        self.projects.insert(project_id, Project::new());
+    pub fn start_new_project(
+        &mut self,
+        project_id: ProjectID,
+        session: SessionID,
+        world: &mut World,
+    ) {
+        if !self.may_plan(session, world) || self.project_exists(project_id, world) {
+            return;
+        }
+        self.journal(JournalCommand::StartNewProject(project_id), session, world);
+        let project = Project {
+            info: ProjectInfo::new(player_of(session)),
+            based_on: COption(Some(self.master_plan.latest_step_id())),
+            ..Project::new()
+        };
+        self.projects.insert(project_id, project);
+    }
+
+    pub fn add_project(
+        &mut self,
+        project_id: ProjectID,
+        project: &Project,
+        session: SessionID,
+        world: &mut World,
+    ) {
+        if !self.may_plan(session, world) || self.project_exists(project_id, world) {
+            return;
+        }
+        self.journal(JournalCommand::AddProject(project_id, project.clone()), session, world);
+        // the review state is only ever changed by the review workflow,
+        // added projects start out as drafts of whoever added them
+        let project = Project {
+            info: ProjectInfo {
+                title: project.info.title.clone(),
+                description: project.info.description.clone(),
+                ..ProjectInfo::new(player_of(session))
+            },
+            based_on: COption(Some(self.master_plan.latest_step_id())),
+            ..project.clone()
+        };
+        self.projects.insert(project_id, project);
+    }
+
+    // IDs are chosen by clients, so they could otherwise replace the projects of others
+    fn project_exists(&self, project_id: ProjectID, world: &mut World) -> bool {
+        let exists = self.projects.contains_key(project_id)
+            || self.implemented_projects.contains_key(project_id);
+        if exists {
+            warn(
+                LOG_T,
+                format!("Refused to replace existing project {:?}", project_id),
+                self.id,
+                world,
+            );
+        }
+        exists
+    }
+
+    pub fn implement(&mut self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        if !self.may_plan(session, world) || !self.may_implement(project_id, session, world) {
+            return;
+        }
+
+        if self.is_awaiting_funds(project_id) {
+            warn(
+                LOG_T,
+                format!("Project {:?} is already waiting to be paid for", project_id),
+                self.id,
+                world,
+            );
+            return;
+        }
+
+        let maybe_result = match self.projects.get(project_id) {
+            Some(project) => project.apply_to(&self.master_plan).calculate_result(),
+            None => {
+                error(
+                    LOG_T,
+                    format!("Can't implement unknown project {:?}", project_id),
+                    self.id,
+                    world,
+                );
+                return;
+            }
+        };
+
+        match maybe_result {
+            Ok(result) => {
+                let (actions, _) = self.master_result.actions_to(&result);
+                let cost = actions.construction_cost(&self.master_result, &result);
+                // journaled before it is paid for, replays pay for it again
+                self.journal(JournalCommand::Implement(project_id), session, world);
+                self.awaiting_funds.push((project_id, cost));
+                TreasuryID::global_first(world)
+                    .pay_for_construction(project_id, cost, self.id, world);
+            }
+            Err(err) => {
+                let err_str = match err {
+                    ::descartes::AreaError::LeftOver(string) => {
+                        format!("Implement Plan Error: {}", string)
+                    }
+                    _ => format!("Implement Plan Error: {:?}", err),
+                };
+                error(LOG_T, err_str, self.id, world);
+            }
+        }
+    }
+
+    pub fn on_construction_paid(&mut self, project_id: ProjectID, paid: bool, world: &mut World) {
+        let cost = match self
+            .awaiting_funds
+            .iter()
+            .find(|&&(awaiting_id, _)| awaiting_id == project_id)
+        {
+            Some(&(_, cost)) => cost,
+            None => return,
+        };
+        self.awaiting_funds.retain(|&(awaiting_id, _)| awaiting_id != project_id);
+
+        if !paid {
+            return;
+        }
+
+        // reviews went on while the payment was underway and other projects might have
+        // changed the master plan, so the project might not be implementable anymore
+        let carried_out = match self.implementation_refusal(project_id) {
+            Some(refusal) => {
+                warn(LOG_T, refusal, self.id, world);
+                false
+            }
+            None => self.carry_out(project_id, world),
+        };
+
+        if !carried_out {
+            TreasuryID::global_first(world).refund_construction(project_id, cost, world);
+        }
     }
 
-    pub fn implement(&mut self, project_id: ProjectID, world: &mut World) {
-        let project = self
-            .projects
-            .remove(project_id)
-            .expect("Project should exist");
+    /// Tells `treasury` what building all roads and plants of the master plan would cost,
+    /// which is what their maintenance is based on. Lots are kept up by their households.
+    pub fn report_infrastructure_value(&mut self, treasury: TreasuryID, world: &mut World) {
+        let infrastructure_value = self
+            .master_result
+            .prototypes
+            .values()
+            .filter(|prototype| match prototype.kind {
+                PrototypeKind::Lot(_) => false,
+                _ => true,
+            })
+            .map(|prototype| f64::from(prototype.construction_cost()))
+            .sum();
 
-        self.master_plan = project.apply_to(&self.master_plan);
+        treasury.pay_for_maintenance(infrastructure_value, world);
+    }
 
-        match self.master_plan.calculate_result() {
+    /// Makes the project part of the master plan, returns whether that worked
+    fn carry_out(&mut self, project_id: ProjectID, world: &mut World) -> bool {
+        let new_master_plan = if let Some(project) = self.projects.get(project_id) {
+            project.apply_to(&self.master_plan)
+        } else {
+            error(
+                LOG_T,
+                format!("Can't implement unknown project {:?}", project_id),
+                self.id,
+                world,
+            );
+            return false;
+        };
+
+        match new_master_plan.calculate_result() {
             Ok(result) => {
                 let (actions, new_prototypes) = self.master_result.actions_to(&result);
                 ConstructionID::global_first(world).implement(actions, new_prototypes, world);
+                let mut project = self
+                    .projects
+                    .remove(project_id)
+                    .expect("should have just applied the project");
+                project.change_info(|info| info.status = ProjectStatus::Implemented);
                 self.implemented_projects.insert(project_id, project);
+                self.master_plan = new_master_plan;
                 self.master_result = result;
 
                 self.ui_state.invalidate_all();
+                true
             }
             Err(err) => {
                 let err_str = match err {
@@ -876,6 +1256,7 @@ impl PlanManager {
                     _ => format!("Implement Plan Error: {:?}", err),
                 };
                 error(LOG_T, err_str, self.id, world);
+                false
             }
         }
     }
@@ -884,15 +1265,31 @@ impl PlanManager {
         &mut self,
         project: &Project,
         based_on: &CVec<PrototypeID>,
+        session: SessionID,
         world: &mut World,
     ) {
+        // only the simulation itself develops the city like this, right away and for free
+        if session != server_session() {
+            warn(
+                LOG_T,
+                "Only the server may implement artificial projects",
+                self.id,
+                world,
+            );
+            return;
+        }
         if based_on
             .iter()
             .all(|prototype_id| self.master_result.prototypes.contains_key(*prototype_id))
         {
+            // the simulation develops the city again while a journal is replayed,
+            // journaling its projects as well would implement them twice
+            let journal = self.journal.take();
             let project_id = ProjectID::new();
-            self.projects.insert(project_id, project.clone());
-            self.implement(project_id, world);
+            self.add_project(project_id, project, session, world);
+            // households develop their lots right away, the city doesn't review or pay for that
+            self.carry_out(project_id, world);
+            self.journal = journal;
         } else {
             info(
                 LOG_T,
@@ -909,6 +1306,12 @@ pub fn setup(system: &mut ActorSystem) {
     auto_setup(system);
     interaction::auto_setup(system);
     ui::auto_setup(system);
+    plan_files::auto_setup(system);
+    osm_import::auto_setup(system);
+    geojson_export::auto_setup(system);
+    journal::setup(system);
+    review::auto_setup(system);
+    conflicts::auto_setup(system);
 }
 
 pub fn spawn(world: &mut World) -> PlanManagerID {
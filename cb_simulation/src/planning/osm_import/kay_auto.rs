@@ -0,0 +1,32 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl PlanManagerID {
+    pub fn import_osm(self, path: CString, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_import_osm(path, session));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_import_osm(pub CString, pub SessionID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_import_osm(ref path, session), instance, world| {
+            instance.import_osm(path, session, world); Fate::Live
+        }, false
+    );
+}
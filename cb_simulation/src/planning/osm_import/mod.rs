@@ -0,0 +1,244 @@
+use kay::World;
+use compact::CString;
+use descartes::{N, P2};
+use xml::reader::{EventReader, XmlEvent};
+use xml::attribute::OwnedAttribute;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufReader};
+
+use transport::transport_planning::RoadIntent;
+use land_use::zone_planning::{ZoneIntent, LandUse};
+use super::{PlanManager, PlanManagerID, Plan, Project, ProjectID, Gesture, GestureID,
+GestureIntent};
+
+use log::{error, info};
+use access::{server_session, ensure_role, Role, SessionID};
+use super::LOG_T;
+
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+// highways that aren't meant for cars
+const IGNORED_HIGHWAYS: [&str; 12] = [
+    "footway",
+    "path",
+    "cycleway",
+    "steps",
+    "pedestrian",
+    "bridleway",
+    "corridor",
+    "track",
+    "proposed",
+    "construction",
+    "platform",
+    "elevator",
+];
+
+#[derive(Default)]
+struct OsmWay {
+    node_refs: Vec<u64>,
+    tags: HashMap<String, String>,
+}
+
+fn attribute<'a>(attributes: &'a [OwnedAttribute], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|attribute| attribute.name.local_name == name)
+        .map(|attribute| attribute.value.as_str())
+}
+
+fn invalid_data<E: ToString>(err: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+/// Projects lat/lon onto the plane, equirectangular around `origin`, north is +y
+fn project((lat, lon): (f64, f64), (origin_lat, origin_lon): (f64, f64)) -> P2 {
+    let meters_per_degree = EARTH_RADIUS * ::std::f64::consts::PI / 180.0;
+    P2::new(
+        ((lon - origin_lon) * meters_per_degree * origin_lat.to_radians().cos()) as N,
+        ((lat - origin_lat) * meters_per_degree) as N,
+    )
+}
+
+fn default_lanes_per_direction(highway: &str) -> u8 {
+    match highway {
+        "motorway" | "trunk" | "primary" => 2,
+        _ => 1,
+    }
+}
+
+fn road_intent(highway: &str, tags: &HashMap<String, String>) -> RoadIntent {
+    let tag = |key: &str| tags.get(key).and_then(|value| value.parse::<u8>().ok());
+    let is_roundabout = tags.get("junction").map(|value| value.as_str()) == Some("roundabout");
+    let oneway = match tags.get("oneway").map(|value| value.as_str()) {
+        Some("yes") | Some("true") | Some("1") | Some("-1") => true,
+        Some(_) => false,
+        None => highway == "motorway" || is_roundabout,
+    };
+
+    let (forward, backward) = match (tag("lanes:forward"), tag("lanes:backward"), tag("lanes")) {
+        (Some(forward), Some(backward), _) => (forward, backward),
+        (Some(forward), None, Some(lanes)) => (forward, lanes.saturating_sub(forward)),
+        (None, Some(backward), Some(lanes)) => (lanes.saturating_sub(backward), backward),
+        (_, _, Some(lanes)) if oneway => (lanes, 0),
+        (_, _, Some(lanes)) => ((lanes + 1) / 2, lanes / 2),
+        _ if oneway => (default_lanes_per_direction(highway), 0),
+        _ => (
+            default_lanes_per_direction(highway),
+            default_lanes_per_direction(highway),
+        ),
+    };
+
+    // narrow two-way streets are often tagged with a single lane they share,
+    // they still need a lane in each direction
+    RoadIntent::new(forward.max(1), if oneway { 0 } else { backward.max(1) })
+}
+
+fn land_use(landuse: &str) -> Option<LandUse> {
+    match landuse {
+        "residential" => Some(LandUse::Residential),
+        "commercial" | "retail" => Some(LandUse::Commercial),
+        "industrial" | "port" => Some(LandUse::Industrial),
+        "farmland" | "farmyard" | "meadow" | "orchard" | "vineyard" | "allotments"
+        | "greenhouse_horticulture" => Some(LandUse::Agricultural),
+        "recreation_ground" | "grass" | "village_green" => Some(LandUse::Recreational),
+        "civic" | "institutional" | "education" | "religious" | "military" => {
+            Some(LandUse::Official)
+        }
+        _ => None,
+    }
+}
+
+/// Reads a local `.osm` XML extract into a plan: `highway=*` ways become roads,
+/// closed `landuse=*` ways become zones. Multipolygon relations are not supported.
+pub fn read_osm(path: &str) -> io::Result<Plan> {
+    let reader = BufReader::new(File::open(path)?);
+
+    let mut nodes = HashMap::<u64, (f64, f64)>::new();
+    let mut ways = Vec::<OsmWay>::new();
+    let mut current_way: Option<OsmWay> = None;
+
+    for event in EventReader::new(reader) {
+        match event.map_err(invalid_data)? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => match name.local_name.as_str() {
+                "node" => {
+                    let id = attribute(&attributes, "id").and_then(|id| id.parse().ok());
+                    let lat = attribute(&attributes, "lat").and_then(|lat| lat.parse().ok());
+                    let lon = attribute(&attributes, "lon").and_then(|lon| lon.parse().ok());
+                    if let (Some(id), Some(lat), Some(lon)) = (id, lat, lon) {
+                        nodes.insert(id, (lat, lon));
+                    }
+                }
+                "way" => current_way = Some(OsmWay::default()),
+                "nd" => {
+                    if let (Some(way), Some(node_ref)) = (
+                        current_way.as_mut(),
+                        attribute(&attributes, "ref").and_then(|node_ref| node_ref.parse().ok()),
+                    ) {
+                        way.node_refs.push(node_ref);
+                    }
+                }
+                "tag" => {
+                    if let (Some(way), Some(key), Some(value)) = (
+                        current_way.as_mut(),
+                        attribute(&attributes, "k"),
+                        attribute(&attributes, "v"),
+                    ) {
+                        way.tags.insert(key.to_owned(), value.to_owned());
+                    }
+                }
+                _ => {}
+            },
+            XmlEvent::EndElement { name } => {
+                if name.local_name == "way" {
+                    ways.extend(current_way.take());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if nodes.is_empty() {
+        return Err(invalid_data("OSM file contains no nodes"));
+    }
+
+    let origin = {
+        let (lat_sum, lon_sum) = nodes
+            .values()
+            .fold((0.0, 0.0), |(lat_sum, lon_sum), &(lat, lon)| {
+                (lat_sum + lat, lon_sum + lon)
+            });
+        (lat_sum / nodes.len() as f64, lon_sum / nodes.len() as f64)
+    };
+
+    let mut gestures = Vec::new();
+
+    for way in ways {
+        let mut points = way
+            .node_refs
+            .iter()
+            .filter_map(|node_ref| nodes.get(node_ref))
+            .map(|&lat_lon| project(lat_lon, origin))
+            .collect::<Vec<_>>();
+
+        if let Some(highway) = way.tags.get("highway") {
+            if IGNORED_HIGHWAYS.contains(&highway.as_str()) || points.len() < 2 {
+                continue;
+            }
+            if way.tags.get("oneway").map(|value| value.as_str()) == Some("-1") {
+                points.reverse();
+            }
+            let intent = GestureIntent::Road(road_intent(highway, &way.tags));
+            gestures.push((GestureID::new(), Gesture::new(points.into(), intent)));
+        } else if let Some(land_use) = way.tags.get("landuse").and_then(|value| land_use(value))
+        {
+            let is_closed =
+                way.node_refs.len() > 3 && way.node_refs.first() == way.node_refs.last();
+            if is_closed {
+                // zone gestures are closed implicitly
+                points.pop();
+                let intent = GestureIntent::Zone(ZoneIntent::LandUse(land_use));
+                gestures.push((GestureID::new(), Gesture::new(points.into(), intent)));
+            }
+        }
+    }
+
+    Ok(Plan::from_gestures(gestures))
+}
+
+impl PlanManager {
+    pub fn import_osm(&mut self, path: &CString, session: SessionID, world: &mut World) {
+        // paths are on the server, so only admins may choose them
+        if !ensure_role(session, Role::Admin, "import an OSM file", self.id, world) {
+            return;
+        }
+
+        match read_osm(path) {
+            Ok(plan) => {
+                let n_gestures = plan.gestures.len();
+                let project = Project::from_plan(plan);
+                self.add_project(ProjectID::new(), &project, server_session(), world);
+                info(
+                    LOG_T,
+                    format!(
+                        "Imported {} gestures from {} as a new project",
+                        n_gestures, &**path
+                    ),
+                    self.id,
+                    world,
+                );
+            }
+            Err(err) => error(
+                LOG_T,
+                format!("Couldn't import OSM file {}: {}", &**path, err),
+                self.id,
+                world,
+            ),
+        }
+    }
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,56 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl PlanManagerID {
+    pub fn export_master_plan(self, path: CString, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_export_master_plan(path, session));
+    }
+    
+    pub fn export_project(self, project_id: ProjectID, path: CString, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_export_project(project_id, path, session));
+    }
+    
+    pub fn import_plan(self, path: CString, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_import_plan(path, session));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_export_master_plan(pub CString, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_export_project(pub ProjectID, pub CString, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_import_plan(pub CString, pub SessionID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_export_master_plan(ref path, session), instance, world| {
+            instance.export_master_plan(path, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_export_project(project_id, ref path, session), instance, world| {
+            instance.export_project(project_id, path, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_import_plan(ref path, session), instance, world| {
+            instance.import_plan(path, session, world); Fate::Live
+        }, false
+    );
+}
@@ -0,0 +1,152 @@
+use kay::World;
+use compact::CString;
+use serde_json;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+
+use super::{PlanManager, PlanManagerID, PlanHistory, Plan, Project, ProjectID, Gesture, GestureID,
+VersionedGesture};
+
+use log::{error, info};
+use access::{server_session, ensure_role, Role, SessionID};
+use super::LOG_T;
+
+// bump this whenever Gesture or any of the intents change their serialized layout
+pub const PLAN_FILE_VERSION: u32 = 1;
+
+/// A standalone, human-readable plan that can be shared between cities.
+/// Gesture IDs are only meaningful within the file, they get replaced on import.
+#[derive(Serialize, Deserialize)]
+pub struct PlanFile {
+    pub version: u32,
+    pub gestures: Vec<(GestureID, Gesture)>,
+}
+
+impl PlanFile {
+    pub fn from_history(history: &PlanHistory) -> PlanFile {
+        PlanFile {
+            version: PLAN_FILE_VERSION,
+            gestures: history
+                .gestures
+                .pairs()
+                .filter(|(_, VersionedGesture(gesture, _))| !gesture.deleted)
+                .map(|(gesture_id, VersionedGesture(gesture, _))| (*gesture_id, gesture.clone()))
+                .collect(),
+        }
+    }
+
+    pub fn write(&self, path: &str) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self).map_err(io::Error::from)
+    }
+
+    pub fn read(path: &str) -> io::Result<PlanFile> {
+        let reader = BufReader::new(File::open(path)?);
+        let plan_file: PlanFile = serde_json::from_reader(reader).map_err(io::Error::from)?;
+
+        if plan_file.version == PLAN_FILE_VERSION {
+            Ok(plan_file)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Plan file has version {}, expected {}",
+                    plan_file.version, PLAN_FILE_VERSION
+                ),
+            ))
+        }
+    }
+
+    pub fn into_project(self) -> Project {
+        Project::from_plan(Plan::from_gestures(
+            self.gestures
+                .into_iter()
+                .map(|(_, gesture)| (GestureID::new(), gesture)),
+        ))
+    }
+}
+
+impl PlanManager {
+    pub fn export_master_plan(&mut self, path: &CString, session: SessionID, world: &mut World) {
+        // paths are on the server, so only admins may choose them
+        if !ensure_role(session, Role::Admin, "export the master plan", self.id, world) {
+            return;
+        }
+        self.write_plan_file(&PlanFile::from_history(&self.master_plan), path, world);
+    }
+
+    pub fn export_project(
+        &mut self,
+        project_id: ProjectID,
+        path: &CString,
+        session: SessionID,
+        world: &mut World,
+    ) {
+        if !ensure_role(session, Role::Admin, "export a project", self.id, world) {
+            return;
+        }
+        let maybe_plan_file = self
+            .projects
+            .get(project_id)
+            .or_else(|| self.implemented_projects.get(project_id))
+            .map(|project| PlanFile::from_history(&project.apply_to(&PlanHistory::new())));
+
+        if let Some(plan_file) = maybe_plan_file {
+            self.write_plan_file(&plan_file, path, world);
+        } else {
+            error(
+                LOG_T,
+                format!("Can't export unknown project {:?}", project_id),
+                self.id,
+                world,
+            );
+        }
+    }
+
+    fn write_plan_file(&self, plan_file: &PlanFile, path: &str, world: &mut World) {
+        match plan_file.write(path) {
+            Ok(()) => info(
+                LOG_T,
+                format!("Exported {} gestures to {}", plan_file.gestures.len(), path),
+                self.id,
+                world,
+            ),
+            Err(err) => error(
+                LOG_T,
+                format!("Couldn't export plan to {}: {}", path, err),
+                self.id,
+                world,
+            ),
+        }
+    }
+
+    pub fn import_plan(&mut self, path: &CString, session: SessionID, world: &mut World) {
+        if !ensure_role(session, Role::Admin, "import a plan", self.id, world) {
+            return;
+        }
+        match PlanFile::read(path) {
+            Ok(plan_file) => {
+                let n_gestures = plan_file.gestures.len();
+                let project_id = ProjectID::new();
+                let session = server_session();
+                self.add_project(project_id, &plan_file.into_project(), session, world);
+                self.implement(project_id, session, world);
+                info(
+                    LOG_T,
+                    format!("Imported {} gestures from {}", n_gestures, &**path),
+                    self.id,
+                    world,
+                );
+            }
+            Err(err) => error(
+                LOG_T,
+                format!("Couldn't import plan from {}: {}", &**path, err),
+                self.id,
+                world,
+            ),
+        }
+    }
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,80 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+
+
+impl PlanManagerID {
+    pub fn set_project_info(self, project_id: ProjectID, title: CString, description: CString, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_set_project_info(project_id, title, description, session));
+    }
+    
+    pub fn propose(self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_propose(project_id, session));
+    }
+    
+    pub fn withdraw(self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_withdraw(project_id, session));
+    }
+    
+    pub fn approve(self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_approve(project_id, session));
+    }
+    
+    pub fn reject(self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanManager_reject(project_id, session));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_set_project_info(pub ProjectID, pub CString, pub CString, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_propose(pub ProjectID, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_withdraw(pub ProjectID, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_approve(pub ProjectID, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanManager_reject(pub ProjectID, pub SessionID);
+
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_set_project_info(project_id, ref title, ref description, session), instance, world| {
+            instance.set_project_info(project_id, title, description, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_propose(project_id, session), instance, world| {
+            instance.propose(project_id, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_withdraw(project_id, session), instance, world| {
+            instance.withdraw(project_id, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_approve(project_id, session), instance, world| {
+            instance.approve(project_id, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<PlanManager, _, _>(
+        |&MSG_PlanManager_reject(project_id, session), instance, world| {
+            instance.reject(project_id, session, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,155 @@
+use kay::World;
+use compact::CString;
+use access::{SessionID, player_of};
+use tuning::required_project_approvals;
+use planning::{PlanManager, PlanManagerID, ProjectID, ProjectStatus, may_manage};
+use planning::journal::JournalCommand;
+use log::{info, warn};
+const LOG_T: &str = "Project Review";
+
+// Owners propose their drafts, other players approve or reject them, and once enough
+// of them approved, the owner can implement the project. Just as many rejections reject it.
+// Withdrawing a proposal turns it back into a draft, which throws away all votes so far.
+
+impl PlanManager {
+    pub fn set_project_info(
+        &mut self,
+        project_id: ProjectID,
+        title: &CString,
+        description: &CString,
+        session: SessionID,
+        world: &mut World,
+    ) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(
+            JournalCommand::SetProjectInfo(project_id, title.clone(), description.clone()),
+            session,
+            world,
+        );
+
+        if let Some(project) = self.projects.get_mut(project_id) {
+            project.change_info(|info| {
+                info.title = title.clone();
+                info.description = description.clone();
+            });
+        }
+    }
+
+    pub fn propose(&mut self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        if !self.may_edit(project_id, session, world) {
+            return;
+        }
+        self.journal(JournalCommand::Propose(project_id), session, world);
+
+        if let Some(project) = self.projects.get_mut(project_id) {
+            project.change_info(|info| {
+                info.status = if required_project_approvals() == 0 {
+                    ProjectStatus::Approved
+                } else {
+                    ProjectStatus::Proposed
+                };
+            });
+            info(LOG_T, format!("Project {:?} was proposed", project_id), self.id, world);
+        }
+    }
+
+    pub fn withdraw(&mut self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        if !self.may_plan(session, world) {
+            return;
+        }
+        self.journal(JournalCommand::Withdraw(project_id), session, world);
+
+        let refusal = match self.projects.get_mut(project_id) {
+            None => format!("Can't withdraw unknown project {:?}", project_id),
+            Some(ref project) if !may_manage(project.info(), session) => {
+                format!("Only the owner or an admin may withdraw project {:?}", project_id)
+            }
+            Some(ref project) if project.info().status == ProjectStatus::Draft => {
+                format!("Project {:?} is already a draft", project_id)
+            }
+            Some(project) => {
+                project.change_info(|info| {
+                    info.status = ProjectStatus::Draft;
+                    info.approvals.clear();
+                    info.rejections.clear();
+                });
+                return;
+            }
+        };
+
+        warn(LOG_T, refusal, self.id, world);
+    }
+
+    pub fn approve(&mut self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        self.review(project_id, true, session, world);
+    }
+
+    pub fn reject(&mut self, project_id: ProjectID, session: SessionID, world: &mut World) {
+        self.review(project_id, false, session, world);
+    }
+
+    fn review(
+        &mut self,
+        project_id: ProjectID,
+        approve: bool,
+        session: SessionID,
+        world: &mut World,
+    ) {
+        if !self.may_plan(session, world) {
+            return;
+        }
+        self.journal(JournalCommand::Review(project_id, approve), session, world);
+
+        let reviewer = player_of(session);
+
+        let refusal = match (self.projects.get_mut(project_id), reviewer) {
+            (None, _) => format!("Can't review unknown project {:?}", project_id),
+            (_, None) => "Only players can review projects".to_owned(),
+            (Some(ref project), _) if project.info().status != ProjectStatus::Proposed => format!(
+                "Project {:?} is {:?}, only proposed projects can be reviewed",
+                project_id,
+                project.info().status
+            ),
+            (Some(ref project), _) if project.info().owner.0 == reviewer => {
+                format!("Project {:?} can't be reviewed by its owner", project_id)
+            }
+            (Some(ref project), Some(reviewer))
+                if project.info().approvals.contains(&reviewer)
+                    || project.info().rejections.contains(&reviewer) =>
+            {
+                format!("Project {:?} was already reviewed by this player", project_id)
+            }
+            (Some(project), Some(reviewer)) => {
+                let required_votes = required_project_approvals();
+                project.change_info(|info| {
+                    if approve {
+                        info.approvals.push(reviewer);
+                        if info.approvals.len() >= required_votes {
+                            info.status = ProjectStatus::Approved;
+                        }
+                    } else {
+                        info.rejections.push(reviewer);
+                        if info.rejections.len() >= required_votes {
+                            info.status = ProjectStatus::Rejected;
+                        }
+                    }
+                });
+                let outcome = match project.info().status {
+                    ProjectStatus::Approved => "was approved",
+                    ProjectStatus::Rejected => "was rejected",
+                    _ if approve => "got an approval",
+                    _ => "got a rejection",
+                };
+                info(LOG_T, format!("Project {:?} {}", project_id, outcome), self.id, world);
+                return;
+            }
+        };
+
+        warn(LOG_T, refusal, self.id, world);
+    }
+}
+
+pub mod kay_auto;
+pub use self::kay_auto::*;
@@ -35,14 +35,19 @@ impl PlanningUIID {
         world.send(self.as_raw(), MSG_PlanningUI_on_plans_update(master_update, project_updates));
     }
     
-    pub fn on_project_preview_update(self, project_id: ProjectID, effective_history: PlanHistory, result_update: PlanResultUpdate, new_actions: ActionGroups, world: &mut World) {
-        world.send(self.as_raw(), MSG_PlanningUI_on_project_preview_update(project_id, effective_history, result_update, new_actions));
+    pub fn on_project_preview_update(self, project_id: ProjectID, effective_history: PlanHistory, result_update: PlanResultUpdate, new_actions: ActionGroups, construction_cost: f64, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_project_preview_update(project_id, effective_history, result_update, new_actions, construction_cost));
+    }
+    
+    pub fn on_project_conflicts(self, project_id: ProjectID, conflicts: ProjectConflicts, world: &mut World) {
+        world.send(self.as_raw(), MSG_PlanningUI_on_project_conflicts(project_id, conflicts));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<PlanningUIRepresentative>();
         system.register_trait_message::<MSG_PlanningUI_on_plans_update>();
         system.register_trait_message::<MSG_PlanningUI_on_project_preview_update>();
+        system.register_trait_message::<MSG_PlanningUI_on_project_conflicts>();
     }
 
     pub fn register_implementor<A: Actor + PlanningUI>(system: &mut ActorSystem) {
@@ -54,8 +59,14 @@ impl PlanningUIID {
         );
         
         system.add_handler::<A, _, _>(
-            |&MSG_PlanningUI_on_project_preview_update(project_id, ref effective_history, ref result_update, ref new_actions), instance, world| {
-                instance.on_project_preview_update(project_id, effective_history, result_update, new_actions, world); Fate::Live
+            |&MSG_PlanningUI_on_project_preview_update(project_id, ref effective_history, ref result_update, ref new_actions, construction_cost), instance, world| {
+                instance.on_project_preview_update(project_id, effective_history, result_update, new_actions, construction_cost, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<A, _, _>(
+            |&MSG_PlanningUI_on_project_conflicts(project_id, ref conflicts), instance, world| {
+                instance.on_project_conflicts(project_id, conflicts, world); Fate::Live
             }, false
         );
     }
@@ -64,7 +75,9 @@ impl PlanningUIID {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_PlanningUI_on_plans_update(pub PlanHistoryUpdate, pub CHashMap < ProjectID , ProjectUpdate >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_PlanningUI_on_project_preview_update(pub ProjectID, pub PlanHistory, pub PlanResultUpdate, pub ActionGroups);
+struct MSG_PlanningUI_on_project_preview_update(pub ProjectID, pub PlanHistory, pub PlanResultUpdate, pub ActionGroups, pub f64);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PlanningUI_on_project_conflicts(pub ProjectID, pub ProjectConflicts);
 
 
 
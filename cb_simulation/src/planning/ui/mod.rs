@@ -2,6 +2,7 @@ use kay::World;
 use compact::CHashMap;
 use super::{PlanHistory, PlanHistoryUpdate, ProjectID, ProjectUpdate,
 PlanResultUpdate, ActionGroups};
+use super::conflicts::ProjectConflicts;
 
 pub trait PlanningUI {
     fn on_plans_update(
@@ -17,6 +18,14 @@ pub trait PlanningUI {
         effective_history: &PlanHistory,
         result_update: &PlanResultUpdate,
         new_actions: &ActionGroups,
+        construction_cost: f64,
+        _world: &mut World,
+    );
+
+    fn on_project_conflicts(
+        &mut self,
+        _project_id: ProjectID,
+        conflicts: &ProjectConflicts,
         _world: &mut World,
     );
 }
@@ -0,0 +1,217 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct CountedID {
+    _raw_id: RawID
+}
+
+pub struct CountedRepresentative;
+
+impl ActorOrActorTrait for CountedRepresentative {
+    type ID = CountedID;
+}
+
+impl TypedID for CountedID {
+    type Target = CountedRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        CountedID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<A: Actor + Counted> TraitIDFrom<A> for CountedID {}
+
+impl CountedID {
+    pub fn report_count(self, reporter: CityReporterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Counted_report_count(reporter));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<CountedRepresentative>();
+        system.register_trait_message::<MSG_Counted_report_count>();
+    }
+
+    pub fn register_implementor<A: Actor + Counted>(system: &mut ActorSystem) {
+        system.register_implementor::<A, CountedRepresentative>();
+        system.add_handler::<A, _, _>(
+            |&MSG_Counted_report_count(reporter), instance, world| {
+                instance.report_count(reporter, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Counted_report_count(pub CityReporterID);
+
+impl Actor for CityReporter {
+    type ID = CityReporterID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct CityReporterID {
+    _raw_id: RawID
+}
+
+impl TypedID for CityReporterID {
+    type Target = CityReporter;
+
+    fn from_raw(id: RawID) -> Self {
+        CityReporterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl CityReporterID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = CityReporterID::from_raw(world.allocate_instance_id::<CityReporter>());
+        let swarm = world.local_broadcast::<CityReporter>();
+        world.send(swarm, MSG_CityReporter_spawn(id, ));
+        id
+    }
+
+    pub fn refresh(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityReporter_refresh());
+    }
+
+    pub fn counted(self, kind: CountedKind, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityReporter_counted(kind));
+    }
+
+    pub fn counted_cars(self, n_cars: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityReporter_counted_cars(n_cars));
+    }
+
+    pub fn on_construction_queue(self, n_action_groups: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityReporter_on_construction_queue(n_action_groups));
+    }
+
+    pub fn on_market_offers(self, offers: CVec < (Resource , u32) >, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityReporter_on_market_offers(offers));
+    }
+
+    pub fn on_finances(self, balance: f64, ledger: CVec < LedgerPeriod >, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityReporter_on_finances(balance, ledger));
+    }
+
+    pub fn publish(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityReporter_publish());
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityReporter_spawn(pub CityReporterID, );
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityReporter_refresh();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityReporter_counted(pub CountedKind);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityReporter_counted_cars(pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityReporter_on_construction_queue(pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityReporter_on_market_offers(pub CVec < (Resource , u32) >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityReporter_on_finances(pub f64, pub CVec < LedgerPeriod >);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityReporter_publish();
+
+impl Into<TimeUIID> for CityReporterID {
+    fn into(self) -> TimeUIID {
+        TimeUIID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<LogRecipientID> for CityReporterID {
+    fn into(self) -> LogRecipientID {
+        LogRecipientID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    CountedID::register_trait(system);
+    CountedID::register_implementor::<Lane>(system);
+    CountedID::register_implementor::<SwitchLane>(system);
+    CountedID::register_implementor::<Trip>(system);
+    CountedID::register_implementor::<Building>(system);
+    CountedID::register_implementor::<VacantLot>(system);
+    CountedID::register_implementor::<Plant>(system);
+    CountedID::register_implementor::<Family>(system);
+    CountedID::register_implementor::<GroceryShop>(system);
+    CountedID::register_implementor::<GrainFarm>(system);
+    CountedID::register_implementor::<CowFarm>(system);
+    CountedID::register_implementor::<VegetableFarm>(system);
+    CountedID::register_implementor::<Mill>(system);
+    CountedID::register_implementor::<Bakery>(system);
+    CountedID::register_implementor::<NeighboringTownTrade>(system);
+    TimeUIID::register_implementor::<CityReporter>(system);
+    LogRecipientID::register_implementor::<CityReporter>(system);
+    system.add_spawner::<CityReporter, _, _>(
+        |&MSG_CityReporter_spawn(id, ), world| {
+            CityReporter::spawn(id, world)
+        }, false
+    );
+
+    system.add_handler::<CityReporter, _, _>(
+        |&MSG_CityReporter_refresh(), instance, world| {
+            instance.refresh(world); Fate::Live
+        }, false
+    );
+
+    system.add_handler::<CityReporter, _, _>(
+        |&MSG_CityReporter_counted(kind), instance, world| {
+            instance.counted(kind, world); Fate::Live
+        }, false
+    );
+
+    system.add_handler::<CityReporter, _, _>(
+        |&MSG_CityReporter_counted_cars(n_cars), instance, world| {
+            instance.counted_cars(n_cars, world); Fate::Live
+        }, false
+    );
+
+    system.add_handler::<CityReporter, _, _>(
+        |&MSG_CityReporter_on_construction_queue(n_action_groups), instance, world| {
+            instance.on_construction_queue(n_action_groups, world); Fate::Live
+        }, false
+    );
+
+    system.add_handler::<CityReporter, _, _>(
+        |&MSG_CityReporter_on_market_offers(ref offers), instance, world| {
+            instance.on_market_offers(offers, world); Fate::Live
+        }, false
+    );
+
+    system.add_handler::<CityReporter, _, _>(
+        |&MSG_CityReporter_on_finances(balance, ref ledger), instance, world| {
+            instance.on_finances(balance, ledger, world); Fate::Live
+        }, false
+    );
+
+    system.add_handler::<CityReporter, _, _>(
+        |&MSG_CityReporter_publish(), instance, world| {
+            instance.publish(world); Fate::Live
+        }, false
+    );
+}
@@ -0,0 +1,448 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::{CVec, CString};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use time::{TimeID, Instant, TimeOfDay, Date};
+use time::ui::{TimeUI, TimeUIID};
+use log::{Entry, LogLevel, LogRecipient, LogRecipientID, LogID};
+use economy::resources::Resource;
+use economy::market::MarketID;
+use construction::ConstructionID;
+use economy::treasury::{TreasuryID, LedgerPeriod};
+use transport::lane::{Lane, SwitchLane};
+use transport::pathfinding::trip::{Trip, TripStatistics, trip_statistics};
+use land_use::buildings::Building;
+use land_use::vacant_lots::VacantLot;
+use environment::vegetation::Plant;
+use economy::households::household_kinds::family::Family;
+use economy::households::household_kinds::grocery_shop::GroceryShop;
+use economy::households::household_kinds::grain_farm::GrainFarm;
+use economy::households::household_kinds::cow_farm::CowFarm;
+use economy::households::household_kinds::vegetable_farm::VegetableFarm;
+use economy::households::household_kinds::mill::Mill;
+use economy::households::household_kinds::bakery::Bakery;
+use economy::households::household_kinds::neighboring_town_trade::NeighboringTownTrade;
+
+// how many of the newest log entries are kept in a report
+const N_RECENT_LOG_ENTRIES: u32 = 100;
+// counting asks every single lane, building, household and so on,
+// so counts are only renewed with every few refreshes
+const RECOUNT_EVERY_N_REFRESHES: u32 = 10;
+
+// A snapshot of the simulation state, readable from outside of the actor system
+// (for example by the HTTP API). It is rebuilt by the CityReporter on request.
+#[derive(Serialize, Clone, Default)]
+pub struct CityReport {
+    pub time: TimeReport,
+    pub counts: HashMap<CountedKind, usize>,
+    pub cars: usize,
+    pub trips: TripStatistics,
+    pub construction_queue: usize,
+    pub market_offers: HashMap<Resource, usize>,
+    pub recent_log: Vec<LogReportEntry>,
+    pub treasury: TreasuryReport,
+    pub system: SystemReport,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct TimeReport {
+    pub ticks: usize,
+    pub day: usize,
+    pub date: String,
+    pub hour: usize,
+    pub minute: usize,
+    pub speed: u16,
+    pub paused: bool,
+    pub warping: bool,
+}
+
+#[derive(Serialize, Clone, Default)]
+pub struct TreasuryReport {
+    pub balance: f64,
+    /// Oldest day first, the last one is the current day
+    pub ledger: Vec<LedgerPeriod>,
+}
+
+// Statistics of the actor system itself, which are only accessible from the simulation thread
+#[derive(Serialize, Clone, Default)]
+pub struct SystemReport {
+    pub fps: f32,
+    pub queue_lengths: HashMap<String, usize>,
+    pub message_statistics: HashMap<String, usize>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct LogReportEntry {
+    pub ticks: usize,
+    pub level: LogLevel,
+    pub topic: String,
+    pub message: String,
+    pub from: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct CountsSummary {
+    pub lanes: usize,
+    pub buildings: usize,
+    pub households: usize,
+    pub by_kind: HashMap<CountedKind, usize>,
+}
+
+impl CityReport {
+    pub fn counts_summary(&self) -> CountsSummary {
+        let count_of = |kind| self.counts.get(&kind).cloned().unwrap_or(0);
+
+        CountsSummary {
+            lanes: count_of(CountedKind::Lane),
+            buildings: count_of(CountedKind::Building),
+            households: self
+                .counts
+                .iter()
+                .filter(|&(kind, _)| kind.is_household())
+                .map(|(_, n)| n)
+                .sum(),
+            by_kind: self.counts.clone(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum CountedKind {
+    Lane,
+    SwitchLane,
+    Trip,
+    Building,
+    VacantLot,
+    Plant,
+    Family,
+    GroceryShop,
+    GrainFarm,
+    CowFarm,
+    VegetableFarm,
+    Mill,
+    Bakery,
+    NeighboringTownTrade,
+}
+
+impl CountedKind {
+    pub fn is_household(self) -> bool {
+        match self {
+            CountedKind::Family
+            | CountedKind::GroceryShop
+            | CountedKind::GrainFarm
+            | CountedKind::CowFarm
+            | CountedKind::VegetableFarm
+            | CountedKind::Mill
+            | CountedKind::Bakery
+            | CountedKind::NeighboringTownTrade => true,
+            _ => false,
+        }
+    }
+}
+
+lazy_static! {
+    static ref LATEST_REPORT: RwLock<CityReport> = RwLock::new(CityReport::default());
+}
+
+/// The last report published by the local CityReporter
+pub fn latest_report() -> CityReport {
+    LATEST_REPORT
+        .read()
+        .expect("city report lock shouldn't be poisoned")
+        .clone()
+}
+
+pub trait Counted {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World);
+}
+
+#[derive(Compact, Clone)]
+pub struct CityReporter {
+    id: CityReporterID,
+    time: Option<(Instant, u16, bool, bool)>,
+    counts: CVec<(CountedKind, u32)>,
+    n_cars: u32,
+    construction_queue: u32,
+    market_offers: CVec<(Resource, u32)>,
+    log_entries: CVec<Entry>,
+    log_text: CString,
+    log_text_start: u64,
+    balance: f64,
+    ledger: CVec<LedgerPeriod>,
+    refreshes_since_recount: u32,
+}
+
+impl CityReporter {
+    pub fn spawn(id: CityReporterID, _: &mut World) -> CityReporter {
+        CityReporter {
+            id,
+            time: None,
+            counts: CVec::new(),
+            n_cars: 0,
+            construction_queue: 0,
+            market_offers: CVec::new(),
+            log_entries: CVec::new(),
+            log_text: CString::new(),
+            log_text_start: 0,
+            balance: 0.0,
+            ledger: CVec::new(),
+            refreshes_since_recount: RECOUNT_EVERY_N_REFRESHES,
+        }
+    }
+
+    /// Asks all reported actors for their current state,
+    /// their answers are collected until the next `publish`
+    pub fn refresh(&mut self, world: &mut World) {
+        self.market_offers = CVec::new();
+
+        TimeID::local_first(world).get_info(self.id_as(), world);
+        LogID::local_first(world).get_after(0, N_RECENT_LOG_ENTRIES, self.id_as(), world);
+        MarketID::local_first(world).report_offers(self.id, world);
+        ConstructionID::local_first(world).report_queue(self.id, world);
+        TreasuryID::local_first(world).report_finances(self.id, world);
+
+        if self.refreshes_since_recount >= RECOUNT_EVERY_N_REFRESHES {
+            self.counts = CVec::new();
+            self.n_cars = 0;
+            CountedID::local_broadcast(world).report_count(self.id, world);
+            self.refreshes_since_recount = 0;
+        }
+        self.refreshes_since_recount += 1;
+    }
+
+    pub fn counted(&mut self, kind: CountedKind, _: &mut World) {
+        if let Some(entry) = self.counts.iter_mut().find(|entry| entry.0 == kind) {
+            entry.1 += 1;
+            return;
+        }
+        self.counts.push((kind, 1));
+    }
+
+    pub fn counted_cars(&mut self, n_cars: u32, _: &mut World) {
+        self.n_cars += n_cars;
+    }
+
+    pub fn on_construction_queue(&mut self, n_action_groups: u32, _: &mut World) {
+        self.construction_queue = n_action_groups;
+    }
+
+    pub fn on_market_offers(&mut self, offers: &CVec<(Resource, u32)>, _: &mut World) {
+        self.market_offers = offers.clone();
+    }
+
+    pub fn on_finances(&mut self, balance: f64, ledger: &CVec<LedgerPeriod>, _: &mut World) {
+        self.balance = balance;
+        self.ledger = ledger.clone();
+    }
+
+    /// Replaces the latest report with everything collected since the last `refresh`
+    pub fn publish(&mut self, _: &mut World) {
+        let time = self
+            .time
+            .map(|(instant, speed, paused, warping)| {
+                let (hour, minute) = TimeOfDay::from(instant).hours_minutes();
+                let date = Date::from(instant);
+                TimeReport {
+                    ticks: instant.ticks(),
+                    day: date.day(),
+                    date: date.to_string(),
+                    hour,
+                    minute,
+                    speed,
+                    paused,
+                    warping,
+                }
+            })
+            .unwrap_or_default();
+
+        let recent_log = self
+            .log_entries
+            .iter()
+            .map(|entry| LogReportEntry {
+                ticks: entry.instant().ticks(),
+                level: entry.level(),
+                topic: entry.topic(&self.log_text, self.log_text_start).to_owned(),
+                message: entry.message(&self.log_text, self.log_text_start).to_owned(),
+                from: entry.from().map(|raw_id| format!("{:?}", raw_id)),
+            })
+            .collect();
+
+        let mut latest_report = LATEST_REPORT
+            .write()
+            .expect("city report lock shouldn't be poisoned");
+        // only known to the thread running the actor system, see `report`
+        let system = latest_report.system.clone();
+
+        *latest_report = CityReport {
+            time,
+            counts: self
+                .counts
+                .iter()
+                .map(|&(kind, n)| (kind, n as usize))
+                .collect(),
+            cars: self.n_cars as usize,
+            trips: trip_statistics(),
+            construction_queue: self.construction_queue as usize,
+            market_offers: self
+                .market_offers
+                .iter()
+                .map(|&(resource, n)| (resource, n as usize))
+                .collect(),
+            recent_log,
+            treasury: TreasuryReport {
+                balance: self.balance,
+                ledger: self.ledger.iter().cloned().collect(),
+            },
+            system,
+        };
+    }
+}
+
+impl TimeUI for CityReporter {
+    fn on_time_info(
+        &mut self,
+        current_instant: Instant,
+        speed: u16,
+        paused: bool,
+        warping: bool,
+        _: &mut World,
+    ) {
+        self.time = Some((current_instant, speed, paused, warping));
+    }
+}
+
+impl LogRecipient for CityReporter {
+    fn receive_newest_logs(
+        &mut self,
+        entries: &CVec<Entry>,
+        text: &CString,
+        _effective_last: u32,
+        effective_text_start: u64,
+        _: &mut World,
+    ) {
+        self.log_entries = entries.clone();
+        self.log_text = text.clone();
+        self.log_text_start = effective_text_start;
+    }
+}
+
+impl Counted for Lane {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::Lane, world);
+        if !self.microtraffic.cars.is_empty() {
+            reporter.counted_cars(self.microtraffic.cars.len() as u32, world);
+        }
+    }
+}
+
+impl Counted for SwitchLane {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::SwitchLane, world);
+        if !self.microtraffic.cars.is_empty() {
+            reporter.counted_cars(self.microtraffic.cars.len() as u32, world);
+        }
+    }
+}
+
+impl Counted for Trip {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::Trip, world);
+    }
+}
+
+impl Counted for Building {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::Building, world);
+    }
+}
+
+impl Counted for VacantLot {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::VacantLot, world);
+    }
+}
+
+impl Counted for Plant {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::Plant, world);
+    }
+}
+
+impl Counted for Family {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::Family, world);
+    }
+}
+
+impl Counted for GroceryShop {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::GroceryShop, world);
+    }
+}
+
+impl Counted for GrainFarm {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::GrainFarm, world);
+    }
+}
+
+impl Counted for CowFarm {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::CowFarm, world);
+    }
+}
+
+impl Counted for VegetableFarm {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::VegetableFarm, world);
+    }
+}
+
+impl Counted for Mill {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::Mill, world);
+    }
+}
+
+impl Counted for Bakery {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::Bakery, world);
+    }
+}
+
+impl Counted for NeighboringTownTrade {
+    fn report_count(&mut self, reporter: CityReporterID, world: &mut World) {
+        reporter.counted(CountedKind::NeighboringTownTrade, world);
+    }
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<CityReporter>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) -> CityReporterID {
+    CityReporterID::spawn(world)
+}
+
+/// Publishes everything collected since the last call and asks for a fresh report.
+/// The answers arrive while the following frames are processed, so the latest report
+/// always lags one call behind, but reporting never holds up the simulation.
+pub fn report(system: &mut ActorSystem, reporter: CityReporterID, fps: f32) {
+    let world = &mut system.world();
+    reporter.publish(world);
+    reporter.refresh(world);
+
+    let mut latest_report = LATEST_REPORT
+        .write()
+        .expect("city report lock shouldn't be poisoned");
+    latest_report.system = SystemReport {
+        fps,
+        queue_lengths: system.get_queue_lengths(),
+        message_statistics: system.get_message_statistics(),
+    };
+}
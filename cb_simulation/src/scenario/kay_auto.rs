@@ -0,0 +1,66 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+
+
+impl Actor for ScenarioRunner {
+    type ID = ScenarioRunnerID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)] #[serde(transparent)]
+pub struct ScenarioRunnerID {
+    _raw_id: RawID
+}
+
+impl TypedID for ScenarioRunnerID {
+    type Target = ScenarioRunner;
+
+    fn from_raw(id: RawID) -> Self {
+        ScenarioRunnerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl ScenarioRunnerID {
+    pub fn spawn(time: TimeID, plan_manager: PlanManagerID, timeline: CVec < ( Instant , ScenarioCommand ) >, world: &mut World) -> Self {
+        let id = ScenarioRunnerID::from_raw(world.allocate_instance_id::<ScenarioRunner>());
+        let swarm = world.local_broadcast::<ScenarioRunner>();
+        world.send(swarm, MSG_ScenarioRunner_spawn(id, time, plan_manager, timeline));
+        id
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ScenarioRunner_spawn(pub ScenarioRunnerID, pub TimeID, pub PlanManagerID, pub CVec < ( Instant , ScenarioCommand ) >);
+
+impl Into<SleeperID> for ScenarioRunnerID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    SleeperID::register_implementor::<ScenarioRunner>(system);
+    system.add_spawner::<ScenarioRunner, _, _>(
+        |&MSG_ScenarioRunner_spawn(id, time, plan_manager, ref timeline), world| {
+            ScenarioRunner::spawn(id, time, plan_manager, timeline, world)
+        }, false
+    );
+}
@@ -0,0 +1,265 @@
+use kay::{ActorSystem, World, TypedID};
+use compact::CVec;
+use descartes::P2;
+use serde_json;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::collections::HashMap;
+
+use time::{TimeID, Instant, Duration, ScheduleID, Sleeper, SleeperID};
+use planning::{PlanManagerID, Plan, Project, ProjectID, Gesture, GestureID, GestureIntent};
+use planning::generators::Generator;
+use transport::lane::LaneID;
+use transport::pathfinding::trip::TripCreatorID;
+
+use log::{info, warn};
+use access::server_session;
+const LOG_T: &str = "Scenario";
+
+// bump this whenever the scenario format changes
+pub const SCENARIO_VERSION: u32 = 1;
+
+/// A declarative city setup: projects made of gestures,
+/// plus a timeline of events that implement them and steer the simulation
+#[derive(Serialize, Deserialize)]
+pub struct Scenario {
+    pub version: u32,
+    #[serde(default)]
+    pub projects: Vec<ScenarioProject>,
+    #[serde(default)]
+    pub events: Vec<ScenarioEvent>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScenarioProject {
+    pub name: String,
+    #[serde(default)]
+    pub gestures: Vec<ScenarioGesture>,
+    #[serde(default)]
+    pub generated: Vec<Generator>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScenarioGesture {
+    pub points: Vec<P2>,
+    pub intent: GestureIntent,
+}
+
+/// Simulated time since the start of the scenario
+#[derive(Serialize, Deserialize, Default)]
+pub struct ScenarioTime {
+    #[serde(default)]
+    pub days: usize,
+    #[serde(default)]
+    pub hours: usize,
+    #[serde(default)]
+    pub minutes: usize,
+}
+
+impl ScenarioTime {
+    pub fn as_duration(&self) -> Duration {
+        Duration::from_hours(self.days * 24 + self.hours) + Duration::from_minutes(self.minutes)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScenarioEvent {
+    #[serde(default)]
+    pub at: ScenarioTime,
+    pub action: ScenarioAction,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum ScenarioAction {
+    ImplementProject(String),
+    SetSpeed(u16),
+    /// Spawns this many cars in total, on trips between random lanes
+    SpawnCars(u32),
+    /// Every lane that isn't part of an intersection tries to start this many trips,
+    /// so the number of cars grows with the size of the road network
+    SpawnCarsPerLane(u32),
+}
+
+impl Scenario {
+    pub fn read(path: &str) -> io::Result<Scenario> {
+        let reader = BufReader::new(File::open(path)?);
+        let scenario: Scenario = serde_json::from_reader(reader).map_err(io::Error::from)?;
+
+        if scenario.version == SCENARIO_VERSION {
+            Ok(scenario)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "Scenario has version {}, expected {}",
+                    scenario.version, SCENARIO_VERSION
+                ),
+            ))
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum ScenarioCommand {
+    ImplementProject(ProjectID),
+    SetSpeed(u16),
+    SpawnCars(u32),
+    SpawnCarsPerLane(u32),
+}
+
+#[derive(Compact, Clone)]
+pub struct ScenarioRunner {
+    id: ScenarioRunnerID,
+    time: TimeID,
+    plan_manager: PlanManagerID,
+    // sorted latest first, so due commands can be popped off the end
+    timeline: CVec<(Instant, ScenarioCommand)>,
+}
+
+impl ScenarioRunner {
+    pub fn spawn(
+        id: ScenarioRunnerID,
+        time: TimeID,
+        plan_manager: PlanManagerID,
+        timeline: &CVec<(Instant, ScenarioCommand)>,
+        world: &mut World,
+    ) -> ScenarioRunner {
+        let mut timeline = timeline.clone();
+        timeline.sort_by_key(|&(at, _)| -at.iticks());
+
+        let mut scheduled_at = None;
+        for &(at, _) in timeline.iter() {
+            if scheduled_at != Some(at) {
+                time.wake_up_at(at, id.into(), ScheduleID::new(), world);
+                scheduled_at = Some(at);
+            }
+        }
+
+        ScenarioRunner {
+            id,
+            time,
+            plan_manager,
+            timeline,
+        }
+    }
+
+    fn run(&self, command: ScenarioCommand, world: &mut World) {
+        match command {
+            ScenarioCommand::ImplementProject(project_id) => {
+                self.plan_manager.implement(project_id, server_session(), world)
+            }
+            ScenarioCommand::SetSpeed(speed) => self.time.set_speed(speed, server_session(), world),
+            ScenarioCommand::SpawnCars(n_cars) => {
+                TripCreatorID::local_first(world).spawn_trips(n_cars, world)
+            }
+            ScenarioCommand::SpawnCarsPerLane(tries_per_lane) => {
+                for _ in 0..tries_per_lane {
+                    LaneID::global_broadcast(world).manually_spawn_car_add_lane(world);
+                }
+            }
+        }
+    }
+}
+
+impl Sleeper for ScenarioRunner {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        while self
+            .timeline
+            .last()
+            .map(|&(at, _)| at <= current_instant)
+            .unwrap_or(false)
+        {
+            let (_, command) = self
+                .timeline
+                .pop()
+                .expect("just checked that there are due commands");
+            self.run(command, world);
+        }
+    }
+}
+
+/// Adds the scenario's projects to the plan manager and schedules its events,
+/// relative to `start`
+pub fn start(
+    scenario: Scenario,
+    start: Instant,
+    time: TimeID,
+    plan_manager: PlanManagerID,
+    world: &mut World,
+) -> ScenarioRunnerID {
+    let mut project_ids = HashMap::new();
+
+    for scenario_project in scenario.projects {
+        let project_id = ProjectID::new();
+        let generated_gestures = scenario_project.generated.iter().flat_map(|generator| {
+            generator
+                .generate()
+                .gestures
+                .pairs()
+                .map(|(gesture_id, gesture)| (*gesture_id, gesture.clone()))
+                .collect::<Vec<_>>()
+        });
+        let plan = Plan::from_gestures(
+            scenario_project
+                .gestures
+                .into_iter()
+                .map(|gesture| {
+                    (
+                        GestureID::new(),
+                        Gesture::new(gesture.points.into(), gesture.intent),
+                    )
+                })
+                .chain(generated_gestures),
+        );
+        let project = Project::from_plan(plan);
+        plan_manager.add_project(project_id, project, server_session(), world);
+        project_ids.insert(scenario_project.name, project_id);
+    }
+
+    let mut timeline = CVec::new();
+
+    for event in scenario.events {
+        let command = match event.action {
+            ScenarioAction::ImplementProject(name) => match project_ids.get(&name) {
+                Some(project_id) => ScenarioCommand::ImplementProject(*project_id),
+                None => {
+                    warn(
+                        LOG_T,
+                        format!("Skipping event for unknown project {}", name),
+                        plan_manager,
+                        world,
+                    );
+                    continue;
+                }
+            },
+            ScenarioAction::SetSpeed(speed) => ScenarioCommand::SetSpeed(speed),
+            ScenarioAction::SpawnCars(n_cars) => ScenarioCommand::SpawnCars(n_cars),
+            ScenarioAction::SpawnCarsPerLane(tries_per_lane) => {
+                ScenarioCommand::SpawnCarsPerLane(tries_per_lane)
+            }
+        };
+        timeline.push((start + event.at.as_duration(), command));
+    }
+
+    let n_events = timeline.len();
+    let runner = ScenarioRunnerID::spawn(time, plan_manager, timeline, world);
+    info(
+        LOG_T,
+        format!(
+            "Started scenario with {} projects and {} events",
+            project_ids.len(),
+            n_events
+        ),
+        runner,
+        world,
+    );
+    runner
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ScenarioRunner>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
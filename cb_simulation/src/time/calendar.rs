@@ -0,0 +1,251 @@
+use super::units::{Instant, Duration, TimeOfDay, TimeOfDayRange, TICKS_PER_SIM_MINUTE,
+BEGINNING_TIME_OF_DAY, MINUTES_PER_DAY};
+
+const DAYS_PER_WEEK: usize = 7;
+// seasons are shortened to four weeks, so a city sees all of them in a reasonable time
+const DAYS_PER_SEASON: usize = 4 * DAYS_PER_WEEK;
+const SEASONS_PER_YEAR: usize = 4;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    pub fn from_idx(idx: usize) -> Weekday {
+        match idx % DAYS_PER_WEEK {
+            0 => Weekday::Monday,
+            1 => Weekday::Tuesday,
+            2 => Weekday::Wednesday,
+            3 => Weekday::Thursday,
+            4 => Weekday::Friday,
+            5 => Weekday::Saturday,
+            _ => Weekday::Sunday,
+        }
+    }
+
+    pub fn is_weekend(self) -> bool {
+        self == Weekday::Saturday || self == Weekday::Sunday
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn from_idx(idx: usize) -> Season {
+        match idx % SEASONS_PER_YEAR {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+}
+
+/// A day of the simulated calendar, starting at midnight.
+/// The simulation starts on a Monday at the beginning of spring.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub struct Date {
+    day: u32,
+}
+
+impl Date {
+    pub fn from_day(day: usize) -> Date {
+        Date { day: day as u32 }
+    }
+
+    /// Days since the simulation started
+    pub fn day(self) -> usize {
+        self.day as usize
+    }
+
+    pub fn weekday(self) -> Weekday {
+        Weekday::from_idx(self.day())
+    }
+
+    pub fn season(self) -> Season {
+        Season::from_idx(self.day() / DAYS_PER_SEASON)
+    }
+
+    /// Starting with 0 on the first day of each season
+    pub fn day_of_season(self) -> usize {
+        self.day() % DAYS_PER_SEASON
+    }
+
+    /// Starting with 0 in the year the simulation started
+    pub fn year(self) -> usize {
+        self.day() / (DAYS_PER_SEASON * SEASONS_PER_YEAR)
+    }
+
+    pub fn next(self) -> Date {
+        Date { day: self.day + 1 }
+    }
+
+    pub fn previous(self) -> Date {
+        Date {
+            day: self.day.saturating_sub(1),
+        }
+    }
+
+    /// The instant at `time` on this day, or the very beginning of the simulation
+    /// if that was earlier
+    pub fn at(self, time: TimeOfDay) -> Instant {
+        let (hours, minutes) = time.hours_minutes();
+        let minutes_since_start = (self.day() * MINUTES_PER_DAY + hours * 60 + minutes)
+            .saturating_sub(BEGINNING_TIME_OF_DAY * 60);
+        Instant::new(minutes_since_start * TICKS_PER_SIM_MINUTE as usize)
+    }
+}
+
+impl From<Instant> for Date {
+    fn from(instant: Instant) -> Date {
+        Date::from_day(
+            (BEGINNING_TIME_OF_DAY * 60 + instant.ticks() / TICKS_PER_SIM_MINUTE as usize)
+                / MINUTES_PER_DAY,
+        )
+    }
+}
+
+impl ::std::fmt::Display for Date {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(
+            f,
+            "{:?}, {:?} {}, Year {}",
+            self.weekday(),
+            self.season(),
+            self.day_of_season() + 1,
+            self.year() + 1
+        )
+    }
+}
+
+/// A set of weekdays, like "weekdays" or "weekends"
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Weekdays(u8);
+
+impl Weekdays {
+    pub const ALL: Weekdays = Weekdays(0b111_1111);
+    pub const WORKDAYS: Weekdays = Weekdays(0b001_1111);
+    pub const WEEKEND: Weekdays = Weekdays(0b110_0000);
+
+    pub fn only(weekday: Weekday) -> Weekdays {
+        Weekdays(1 << weekday as u8)
+    }
+
+    pub fn and(self, other: Weekdays) -> Weekdays {
+        Weekdays(self.0 | other.0)
+    }
+
+    pub fn contains(self, weekday: Weekday) -> bool {
+        self.0 & Weekdays::only(weekday).0 != 0
+    }
+}
+
+/// A set of seasons, like "summer only" or "everything but winter"
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Seasons(u8);
+
+impl Seasons {
+    pub const ALL: Seasons = Seasons(0b1111);
+
+    pub fn only(season: Season) -> Seasons {
+        Seasons(1 << season as u8)
+    }
+
+    pub fn and(self, other: Seasons) -> Seasons {
+        Seasons(self.0 | other.0)
+    }
+
+    pub fn except(season: Season) -> Seasons {
+        Seasons(Seasons::ALL.0 & !Seasons::only(season).0)
+    }
+
+    pub fn contains(self, season: Season) -> bool {
+        self.0 & Seasons::only(season).0 != 0
+    }
+}
+
+/// Hours of the day that only apply on some weekdays and in some seasons,
+/// like "weekdays 8-17" or "summer only". A range of hours that goes past midnight
+/// belongs to the day it started on.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub hours: TimeOfDayRange,
+    pub weekdays: Weekdays,
+    pub seasons: Seasons,
+    // how many days before the day they belong to the hours start, see `earlier_by`
+    days_early: u16,
+}
+
+impl Schedule {
+    /// Every day of the year during `hours`
+    pub fn daily(hours: TimeOfDayRange) -> Schedule {
+        Schedule {
+            hours,
+            weekdays: Weekdays::ALL,
+            seasons: Seasons::ALL,
+            days_early: 0,
+        }
+    }
+
+    pub fn on(self, weekdays: Weekdays) -> Schedule {
+        Schedule { weekdays, ..self }
+    }
+
+    pub fn during(self, seasons: Seasons) -> Schedule {
+        Schedule { seasons, ..self }
+    }
+
+    /// Whether the hours that start on `date` are part of the schedule
+    pub fn is_open_on(self, date: Date) -> bool {
+        self.weekdays.contains(date.weekday()) && self.seasons.contains(date.season())
+    }
+
+    /// Shifts the hours, but not the days they belong to. Hours that get shifted
+    /// past midnight still only apply if the day after is part of the schedule,
+    /// so 0:00-8:00 on Mondays only becomes 23:30-7:30 from Sunday to Monday.
+    pub fn earlier_by(self, delta: Duration) -> Schedule {
+        let (start_h, start_m) = self.hours.start.hours_minutes();
+        let start_minutes = start_h * 60 + start_m;
+        let delta_minutes = delta.as_minutes() as usize;
+        let days_early = if delta_minutes > start_minutes {
+            (delta_minutes - start_minutes + MINUTES_PER_DAY - 1) / MINUTES_PER_DAY
+        } else {
+            0
+        };
+
+        Schedule {
+            hours: self.hours.earlier_by(delta),
+            days_early: self.days_early + days_early as u16,
+            ..self
+        }
+    }
+
+    pub fn contains(self, instant: Instant) -> bool {
+        let time = TimeOfDay::from(instant);
+        if !self.hours.contains(time) {
+            return false;
+        }
+
+        let date = Date::from(instant);
+        let started_on = if self.hours.start > self.hours.end && time <= self.hours.end {
+            date.previous()
+        } else {
+            date
+        };
+
+        self.is_open_on(Date::from_day(started_on.day() + self.days_early as usize))
+    }
+}
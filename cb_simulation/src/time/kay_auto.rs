@@ -143,6 +143,22 @@ impl TimeID {
     pub fn wake_up_in(self, remaining_ticks: Ticks, sleeper_id: SleeperID, world: &mut World) {
         world.send(self.as_raw(), MSG_Time_wake_up_in(remaining_ticks, sleeper_id));
     }
+    
+    pub fn wake_up_at(self, at: Instant, sleeper_id: SleeperID, schedule_id: ScheduleID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_wake_up_at(at, sleeper_id, schedule_id));
+    }
+    
+    pub fn wake_up_every(self, interval: Duration, sleeper_id: SleeperID, schedule_id: ScheduleID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_wake_up_every(interval, sleeper_id, schedule_id));
+    }
+    
+    pub fn wake_up_daily_at(self, time: TimeOfDay, sleeper_id: SleeperID, schedule_id: ScheduleID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_wake_up_daily_at(time, sleeper_id, schedule_id));
+    }
+    
+    pub fn cancel_wake_up(self, schedule_id: ScheduleID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_cancel_wake_up(schedule_id));
+    }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
@@ -151,6 +167,14 @@ struct MSG_Time_spawn(pub TimeID, );
 struct MSG_Time_progress();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_wake_up_in(pub Ticks, pub SleeperID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_wake_up_at(pub Instant, pub SleeperID, pub ScheduleID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_wake_up_every(pub Duration, pub SleeperID, pub ScheduleID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_wake_up_daily_at(pub TimeOfDay, pub SleeperID, pub ScheduleID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_cancel_wake_up(pub ScheduleID);
 
 
 #[allow(unused_variables)]
@@ -176,4 +200,28 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.wake_up_in(remaining_ticks, sleeper_id, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_wake_up_at(at, sleeper_id, schedule_id), instance, world| {
+            instance.wake_up_at(at, sleeper_id, schedule_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_wake_up_every(interval, sleeper_id, schedule_id), instance, world| {
+            instance.wake_up_every(interval, sleeper_id, schedule_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_wake_up_daily_at(time, sleeper_id, schedule_id), instance, world| {
+            instance.wake_up_daily_at(time, sleeper_id, schedule_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_cancel_wake_up(schedule_id), instance, world| {
+            instance.cancel_wake_up(schedule_id, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -1,11 +1,16 @@
-use kay::{ActorSystem, World, TypedID};
+use kay::{ActorSystem, World, TypedID, RawID};
 use compact::CVec;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use transport::pathfinding::trip::trips_ongoing;
+use util::random::{Uuid, uuid};
 
 mod units;
+mod calendar;
 pub mod ui;
 
 pub use self::units::{Instant, Ticks, Duration, TICKS_PER_SIM_MINUTE, TICKS_PER_SIM_SECOND,
 TimeOfDay, TimeOfDayRange};
+pub use self::calendar::{Date, Weekday, Weekdays, Season, Seasons, Schedule};
 
 pub trait Temporal {
     fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World);
@@ -15,12 +20,60 @@ pub trait Sleeper {
     fn wake(&mut self, current_instant: Instant, world: &mut World);
 }
 
-#[derive(Compact, Clone)]
+// mirrors the current instant of the local Time actor for code outside of the actor system
+static LATEST_INSTANT: AtomicUsize = AtomicUsize::new(0);
+
+/// The last instant the local Time actor progressed to
+pub fn latest_instant() -> Instant {
+    Instant::new(LATEST_INSTANT.load(Ordering::SeqCst))
+}
+
+/// Identifies a scheduled wake up, so it can be cancelled later.
+/// Chosen by whoever schedules the wake up.
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub struct ScheduleID(pub Uuid);
+
+impl ScheduleID {
+    pub fn new() -> ScheduleID {
+        ScheduleID(uuid())
+    }
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub enum Repetition {
+    Once,
+    Every(Duration),
+    DailyAt(TimeOfDay),
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+struct WakeUp {
+    at: Instant,
+    sleeper: SleeperID,
+    schedule_id: Option<ScheduleID>,
+    repetition: Repetition,
+}
+
+// how many single ticks are done per frame while stepping or warping
+const MAX_TICKS_PER_PROGRESS: u32 = 32;
+
+// warps never skip more at once, so Temporal actors aren't ticked with a huge dt
+const MAX_WARP_JUMP_TICKS: usize = TICKS_PER_SIM_MINUTE as usize;
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Time {
     id: TimeID,
     current_instant: Instant,
-    sleepers: CVec<(Instant, SleeperID)>,
+    // latest wake up first
+    sleepers: CVec<WakeUp>,
     speed: u16,
+    paused: bool,
+    // ticks that were requested by `step`, done even while paused
+    #[serde(skip)]
+    pending_steps: u32,
+    // while set, time fast-forwards to this instant, see `skip_to_next_morning`
+    #[serde(skip)]
+    warp_until: Option<Instant>,
 }
 
 impl Time {
@@ -30,41 +83,192 @@ impl Time {
             current_instant: Instant::new(0),
             sleepers: CVec::new(),
             speed: 1,
+            paused: false,
+            pending_steps: 0,
+            warp_until: None,
         }
     }
 
     pub fn progress(&mut self, world: &mut World) {
-        for _ in 0..self.speed {
-            TemporalID::global_broadcast(world).tick(
-                1.0 / (TICKS_PER_SIM_SECOND as f32),
-                self.current_instant,
-                world,
-            );
-            while self
+        if let Some(warp_until) = self.warp_until {
+            self.warp(warp_until, world);
+        } else if self.pending_steps > 0 {
+            let n_steps = self.pending_steps.min(MAX_TICKS_PER_PROGRESS);
+            self.pending_steps -= n_steps;
+            for _ in 0..n_steps {
+                self.advance(1, world);
+            }
+        } else if !self.paused {
+            for _ in 0..self.speed {
+                self.advance(1, world);
+            }
+        }
+        self.mirror_current_instant();
+    }
+
+    // Moves on by `n_ticks`, but only ticks Temporal actors and wakes sleepers once,
+    // at the last of those ticks and with a dt that makes up for the skipped ones
+    fn advance(&mut self, n_ticks: usize, world: &mut World) {
+        self.current_instant += Ticks(n_ticks as u32 - 1);
+        TemporalID::global_broadcast(world).tick(
+            n_ticks as f32 / (TICKS_PER_SIM_SECOND as f32),
+            self.current_instant,
+            world,
+        );
+        while self
+            .sleepers
+            .last()
+            .map(|wake_up| wake_up.at < self.current_instant)
+            .unwrap_or(false)
+        {
+            let wake_up = self
                 .sleepers
-                .last()
-                .map(|&(end, _)| end < self.current_instant)
-                .unwrap_or(false)
-            {
-                let (_, sleeper) = self
-                    .sleepers
-                    .pop()
-                    .expect("just checked that there are sleepers");
-                sleeper.wake(self.current_instant, world);
+                .pop()
+                .expect("just checked that there are sleepers");
+            wake_up.sleeper.wake(self.current_instant, world);
+
+            let next_at = match wake_up.repetition {
+                Repetition::Once => None,
+                // never more than once per tick, even for tiny intervals
+                Repetition::Every(interval) => {
+                    Some(wake_up.at + Ticks::from(interval).max(Ticks(1)))
+                }
+                Repetition::DailyAt(time) => Some(Date::from(wake_up.at).next().at(time)),
+            };
+            if let Some(next_at) = next_at {
+                self.insert_wake_up(WakeUp {
+                    at: next_at,
+                    ..wake_up
+                });
             }
-            self.current_instant += Ticks(1);
         }
+        self.current_instant += Ticks(1);
     }
 
-    pub fn wake_up_in(&mut self, remaining_ticks: Ticks, sleeper_id: SleeperID, _: &mut World) {
-        let wake_up_at = self.current_instant + remaining_ticks;
+    // While there are trips underway, traffic needs every single tick, otherwise
+    // time jumps from one sleeper's wake up to the next, at most a minute at once
+    fn warp(&mut self, warp_until: Instant, world: &mut World) {
+        let traffic_is_quiet = trips_ongoing() == 0;
+
+        for _ in 0..MAX_TICKS_PER_PROGRESS {
+            if self.current_instant >= warp_until {
+                self.warp_until = None;
+                return;
+            }
+
+            let n_ticks = if traffic_is_quiet {
+                let until_warp_end = warp_until.ticks() - self.current_instant.ticks();
+                // sleepers are woken at the first tick after their wake up instant
+                let until_next_wake_up = self
+                    .sleepers
+                    .last()
+                    .map_or(MAX_WARP_JUMP_TICKS, |wake_up| {
+                        (wake_up.at.ticks() + 2).saturating_sub(self.current_instant.ticks())
+                    });
+                MAX_WARP_JUMP_TICKS
+                    .min(until_warp_end)
+                    .min(until_next_wake_up)
+                    .max(1)
+            } else {
+                1
+            };
+
+            self.advance(n_ticks, world);
+        }
+    }
+
+    pub fn mirror_current_instant(&self) {
+        LATEST_INSTANT.store(self.current_instant.ticks(), Ordering::SeqCst);
+    }
+
+    /// Drops the wake ups of all sleepers that `is_kept` rejects
+    pub fn retain_sleepers<F: Fn(RawID) -> bool>(&mut self, is_kept: F) {
+        self.sleepers
+            .retain(|wake_up| is_kept(wake_up.sleeper.as_raw()));
+    }
+
+    // the next instant after now that is at `time` of day
+    fn next_occurrence(&self, time: TimeOfDay) -> Instant {
+        let today = Date::from(self.current_instant);
+        if today.at(time) > self.current_instant {
+            today.at(time)
+        } else {
+            today.next().at(time)
+        }
+    }
+
+    fn insert_wake_up(&mut self, wake_up: WakeUp) {
         let maybe_idx = self
             .sleepers
-            .binary_search_by_key(&wake_up_at.iticks(), |&(t, _)| -(t.iticks()));
+            .binary_search_by_key(&-wake_up.at.iticks(), |other| -(other.at.iticks()));
         let insert_idx = match maybe_idx {
             Ok(idx) | Err(idx) => idx,
         };
-        self.sleepers.insert(insert_idx, (wake_up_at, sleeper_id));
+        self.sleepers.insert(insert_idx, wake_up);
+    }
+
+    pub fn wake_up_in(&mut self, remaining_ticks: Ticks, sleeper_id: SleeperID, _: &mut World) {
+        let at = self.current_instant + remaining_ticks;
+        self.insert_wake_up(WakeUp {
+            at,
+            sleeper: sleeper_id,
+            schedule_id: None,
+            repetition: Repetition::Once,
+        });
+    }
+
+    pub fn wake_up_at(
+        &mut self,
+        at: Instant,
+        sleeper_id: SleeperID,
+        schedule_id: ScheduleID,
+        _: &mut World,
+    ) {
+        self.insert_wake_up(WakeUp {
+            at,
+            sleeper: sleeper_id,
+            schedule_id: Some(schedule_id),
+            repetition: Repetition::Once,
+        });
+    }
+
+    /// Wakes up `sleeper_id` after each `interval`, starting one `interval` from now
+    pub fn wake_up_every(
+        &mut self,
+        interval: Duration,
+        sleeper_id: SleeperID,
+        schedule_id: ScheduleID,
+        _: &mut World,
+    ) {
+        self.insert_wake_up(WakeUp {
+            at: self.current_instant + interval,
+            sleeper: sleeper_id,
+            schedule_id: Some(schedule_id),
+            repetition: Repetition::Every(interval),
+        });
+    }
+
+    /// Wakes up `sleeper_id` each day at `time`, starting with its next occurrence
+    pub fn wake_up_daily_at(
+        &mut self,
+        time: TimeOfDay,
+        sleeper_id: SleeperID,
+        schedule_id: ScheduleID,
+        _: &mut World,
+    ) {
+        let at = self.next_occurrence(time);
+        self.insert_wake_up(WakeUp {
+            at,
+            sleeper: sleeper_id,
+            schedule_id: Some(schedule_id),
+            repetition: Repetition::DailyAt(time),
+        });
+    }
+
+    /// Stops all future wake ups of a schedule, including repeated ones
+    pub fn cancel_wake_up(&mut self, schedule_id: ScheduleID, _: &mut World) {
+        self.sleepers
+            .retain(|wake_up| wake_up.schedule_id != Some(schedule_id));
     }
 }
 
@@ -31,8 +31,8 @@ impl TypedID for TimeUIID {
 impl<A: Actor + TimeUI> TraitIDFrom<A> for TimeUIID {}
 
 impl TimeUIID {
-    pub fn on_time_info(self, current_instant: :: time :: Instant, speed: u16, world: &mut World) {
-        world.send(self.as_raw(), MSG_TimeUI_on_time_info(current_instant, speed));
+    pub fn on_time_info(self, current_instant: :: time :: Instant, speed: u16, paused: bool, warping: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_TimeUI_on_time_info(current_instant, speed, paused, warping));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
@@ -43,15 +43,15 @@ impl TimeUIID {
     pub fn register_implementor<A: Actor + TimeUI>(system: &mut ActorSystem) {
         system.register_implementor::<A, TimeUIRepresentative>();
         system.add_handler::<A, _, _>(
-            |&MSG_TimeUI_on_time_info(current_instant, speed), instance, world| {
-                instance.on_time_info(current_instant, speed, world); Fate::Live
+            |&MSG_TimeUI_on_time_info(current_instant, speed, paused, warping), instance, world| {
+                instance.on_time_info(current_instant, speed, paused, warping, world); Fate::Live
             }, false
         );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_TimeUI_on_time_info(pub :: time :: Instant, pub u16);
+struct MSG_TimeUI_on_time_info(pub :: time :: Instant, pub u16, pub bool, pub bool);
 
 
 
@@ -60,15 +60,39 @@ impl TimeID {
         world.send(self.as_raw(), MSG_Time_get_info(requester));
     }
     
-    pub fn set_speed(self, speed: u16, world: &mut World) {
-        world.send(self.as_raw(), MSG_Time_set_speed(speed));
+    pub fn set_speed(self, speed: u16, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_set_speed(speed, session));
+    }
+    
+    pub fn pause(self, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_pause(session));
+    }
+    
+    pub fn resume(self, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_resume(session));
+    }
+    
+    pub fn step(self, n_ticks: u32, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_step(n_ticks, session));
+    }
+    
+    pub fn skip_to_next_morning(self, session: SessionID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Time_skip_to_next_morning(session));
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Time_get_info(pub TimeUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Time_set_speed(pub u16);
+struct MSG_Time_set_speed(pub u16, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_pause(pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_resume(pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_step(pub u32, pub SessionID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Time_skip_to_next_morning(pub SessionID);
 
 
 #[allow(unused_variables)]
@@ -83,8 +107,32 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<Time, _, _>(
-        |&MSG_Time_set_speed(speed), instance, world| {
-            instance.set_speed(speed, world); Fate::Live
+        |&MSG_Time_set_speed(speed, session), instance, world| {
+            instance.set_speed(speed, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_pause(session), instance, world| {
+            instance.pause(session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_resume(session), instance, world| {
+            instance.resume(session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_step(n_ticks, session), instance, world| {
+            instance.step(n_ticks, session, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Time, _, _>(
+        |&MSG_Time_skip_to_next_morning(session), instance, world| {
+            instance.skip_to_next_morning(session, world); Fate::Live
         }, false
     );
 }
\ No newline at end of file
@@ -1,17 +1,71 @@
 use kay::World;
-use super::{Time, TimeID};
+use super::{Time, TimeID, TimeOfDay, TICKS_PER_SIM_MINUTE};
+use access::{SessionID, Role, ensure_role};
+
+// where "skip to next morning" ends up
+const MORNING_HOUR: usize = 7;
+// longer steps would be better served by skipping or running time normally
+const MAX_PENDING_STEPS: u32 = 24 * 60 * TICKS_PER_SIM_MINUTE;
 
 pub trait TimeUI {
-    fn on_time_info(&mut self, current_instant: ::time::Instant, speed: u16, _world: &mut World);
+    fn on_time_info(
+        &mut self,
+        current_instant: ::time::Instant,
+        speed: u16,
+        paused: bool,
+        warping: bool,
+        _world: &mut World,
+    );
 }
 
 impl Time {
     pub fn get_info(&mut self, requester: TimeUIID, world: &mut World) {
-        requester.on_time_info(self.current_instant, self.speed, world);
+        requester.on_time_info(
+            self.current_instant,
+            self.speed,
+            self.paused,
+            self.warp_until.is_some(),
+            world,
+        );
+    }
+
+    pub fn set_speed(&mut self, speed: u16, session: SessionID, world: &mut World) {
+        if ensure_role(session, Role::Admin, "set the speed", self.id, world) {
+            self.speed = speed as u16;
+        }
+    }
+
+    /// Also stops steps and warps that are still underway
+    pub fn pause(&mut self, session: SessionID, world: &mut World) {
+        if ensure_role(session, Role::Admin, "pause", self.id, world) {
+            self.paused = true;
+            self.pending_steps = 0;
+            self.warp_until = None;
+        }
+    }
+
+    pub fn resume(&mut self, session: SessionID, world: &mut World) {
+        if ensure_role(session, Role::Admin, "resume", self.id, world) {
+            self.paused = false;
+        }
+    }
+
+    /// Progresses by exactly `n_ticks`, even while paused, at most a day's worth at once
+    pub fn step(&mut self, n_ticks: u32, session: SessionID, world: &mut World) {
+        if ensure_role(session, Role::Admin, "step", self.id, world) {
+            self.pending_steps = self
+                .pending_steps
+                .saturating_add(n_ticks)
+                .min(MAX_PENDING_STEPS);
+        }
     }
 
-    pub fn set_speed(&mut self, speed: u16, _world: &mut World) {
-        self.speed = speed as u16;
+    /// Fast-forwards to the next morning as quickly as possible,
+    /// afterwards time continues as paused or running as before
+    pub fn skip_to_next_morning(&mut self, session: SessionID, world: &mut World) {
+        if ensure_role(session, Role::Admin, "skip to the next morning", self.id, world) {
+            self.warp_until = Some(self.next_occurrence(TimeOfDay::new(MORNING_HOUR, 0)));
+        }
     }
 }
 
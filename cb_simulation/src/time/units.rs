@@ -72,6 +72,21 @@ impl Instant {
     pub fn iticks(self) -> isize {
         self.0 as isize
     }
+
+    /// Whether any of the `elapsed` ticks up to this instant is one of those that come every
+    /// `period` ticks, offset by `phase`. Time warps tick less often and with a longer dt,
+    /// so throttled updates check this instead of only looking at the current tick.
+    pub fn passed_phase(self, elapsed: usize, period: usize, phase: usize) -> bool {
+        let shifted = self.ticks() + period - phase % period;
+        shifted / period > shifted.saturating_sub(elapsed.max(1)) / period
+    }
+
+    /// Whether the phase will be passed within the next `elapsed` ticks after this instant,
+    /// assuming time keeps passing at the same rate. Lets actors prepare messages that need
+    /// to arrive right before another actor's throttled update.
+    pub fn will_pass_phase(self, elapsed: usize, period: usize, phase: usize) -> bool {
+        Instant(self.0 + elapsed.max(1) as u32).passed_phase(elapsed, period, phase)
+    }
 }
 
 impl<D: Into<Ticks>> ::std::ops::Add<D> for Instant {
@@ -107,8 +122,8 @@ pub struct TimeOfDay {
     minutes_of_day: u16,
 }
 
-const BEGINNING_TIME_OF_DAY: usize = 7;
-const MINUTES_PER_DAY: usize = 60 * 24;
+pub const BEGINNING_TIME_OF_DAY: usize = 7;
+pub const MINUTES_PER_DAY: usize = 60 * 24;
 
 impl TimeOfDay {
     pub fn new(h: usize, m: usize) -> Self {
@@ -10,7 +10,7 @@ use super::lane::connectivity::Interaction;
 use super::microtraffic::LaneLikeID;
 
 use planning::Prototype;
-use construction::{ConstructionID, Constructable, ConstructableID};
+use construction::{ConstructionID, Constructable, ConstructableID, surface};
 use super::transport_planning::{RoadPrototype, LanePrototype, SwitchLanePrototype,
 IntersectionPrototype};
 
@@ -20,7 +20,26 @@ const LOG_T: &str = "Transport Construction";
 use dimensions::{LANE_CONNECTION_TOLERANCE, MAX_SWITCHING_LANE_DISTANCE,
 MIN_SWITCHING_LANE_LENGTH};
 
+const LANE_COST_PER_METER: f32 = 50.0;
+const SWITCH_LANE_COST_PER_METER: f32 = 10.0;
+const INTERSECTION_COST_PER_SQUARE_METER: f32 = 20.0;
+const PAVED_AREA_COST_PER_SQUARE_METER: f32 = 10.0;
+
 impl RoadPrototype {
+    // every lane is its own prototype, so roads cost their length times their number of lanes
+    pub fn construction_cost(&self) -> f32 {
+        match *self {
+            RoadPrototype::Lane(LanePrototype(ref path, _)) => path.length() * LANE_COST_PER_METER,
+            RoadPrototype::SwitchLane(SwitchLanePrototype(ref path)) => {
+                path.length() * SWITCH_LANE_COST_PER_METER
+            }
+            RoadPrototype::Intersection(IntersectionPrototype { ref area, .. }) => {
+                surface(area) * INTERSECTION_COST_PER_SQUARE_METER
+            }
+            RoadPrototype::PavedArea(ref area) => surface(area) * PAVED_AREA_COST_PER_SQUARE_METER,
+        }
+    }
+
     pub fn construct(&self, report_to: ConstructionID, world: &mut World) -> CVec<ConstructableID> {
         match *self {
             RoadPrototype::Lane(LanePrototype(ref path, _)) => {
@@ -89,7 +108,7 @@ impl Constructable for SwitchLane {
     }
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct ConstructionInfo {
     pub length: f32,
     pub path: LinePath,
@@ -3,7 +3,7 @@ use descartes::N;
 use super::{LaneID, SwitchLaneID};
 use transport::microtraffic::LaneLikeID;
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct ConnectivityInfo {
     pub interactions: CVec<Interaction>,
     pub on_intersection: bool,
@@ -18,7 +18,7 @@ impl ConnectivityInfo {
     }
 }
 
-#[derive(Compact, Clone, Default)]
+#[derive(Compact, Clone, Default, Serialize, Deserialize)]
 pub struct SwitchConnectivityInfo {
     pub left: Option<(LaneID, N, N)>,
     pub right: Option<(LaneID, N, N)>,
@@ -26,7 +26,7 @@ pub struct SwitchConnectivityInfo {
     pub right_distance_map: CVec<(N, N)>,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum Interaction {
     Previous {
         previous: LaneID,
@@ -8,7 +8,7 @@ use self::connectivity::{ConnectivityInfo, SwitchConnectivityInfo};
 use super::microtraffic::{Microtraffic, TransferringMicrotraffic};
 use super::pathfinding::PathfindingCore;
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Lane {
     pub id: LaneID,
     pub construction: ConstructionInfo,
@@ -39,7 +39,7 @@ impl Lane {
     }
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct SwitchLane {
     pub id: SwitchLaneID,
     pub construction: ConstructionInfo,
@@ -16,7 +16,7 @@ const LOG_T: &str = "Microtraffic";
 
 // TODO: move all iteration, updates, etc into one huge retain loop (see identical TODO below)
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Microtraffic {
     pub obstacles: CVec<(Obstacle, LaneLikeID)>,
     pub cars: CVec<LaneCar>,
@@ -39,18 +39,14 @@ impl Microtraffic {
     }
 }
 
-// makes "time pass slower" for traffic, so we can still use realistic
-// unit values while traffic happening at a slower pace to be visible
-const MICROTRAFFIC_UNREALISTIC_SLOWDOWN: f32 = 6.0;
-
-#[derive(Compact, Clone, Default)]
+#[derive(Compact, Clone, Default, Serialize, Deserialize)]
 pub struct TransferringMicrotraffic {
     pub left_obstacles: CVec<Obstacle>,
     pub right_obstacles: CVec<Obstacle>,
     pub cars: CVec<TransferringLaneCar>,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct Obstacle {
     pub position: OrderedFloat<f32>,
     pub velocity: f32,
@@ -76,7 +72,7 @@ impl Obstacle {
 use super::pathfinding::trip::{TripID, TripResult, TripFate};
 use super::pathfinding::Link;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct LaneCar {
     pub trip: TripID,
     pub as_obstacle: Obstacle,
@@ -108,7 +104,7 @@ impl DerefMut for LaneCar {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct TransferringLaneCar {
     as_lane_car: LaneCar,
     pub switch_position: f32,
@@ -146,9 +142,9 @@ pub trait LaneLike {
 
 use self::pathfinding::StoredRoutingEntry;
 
-use time::{Temporal, TemporalID};
+use time::{Temporal, TemporalID, TICKS_PER_SIM_SECOND};
+use tuning::{microtraffic_unrealistic_slowdown, traffic_logic_throttling};
 
-const TRAFFIC_LOGIC_THROTTLING: usize = 10;
 const PATHFINDING_THROTTLING: usize = 10;
 
 impl LaneLike for Lane {
@@ -247,12 +243,16 @@ impl Lane {
 
 impl Temporal for Lane {
     fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
-        let dt = dt / MICROTRAFFIC_UNREALISTIC_SLOWDOWN;
+        let elapsed_ticks = (dt * TICKS_PER_SIM_SECOND as f32).round() as usize;
+        let dt = dt / microtraffic_unrealistic_slowdown();
 
         self.construction.progress += dt * 400.0;
 
-        let do_traffic = current_instant.ticks() % TRAFFIC_LOGIC_THROTTLING
-            == self.id.as_raw().instance_id as usize % TRAFFIC_LOGIC_THROTTLING;
+        let do_traffic = current_instant.passed_phase(
+            elapsed_ticks,
+            traffic_logic_throttling(),
+            self.id.as_raw().instance_id as usize,
+        );
 
         let old_green = self.microtraffic.green;
         self.microtraffic.yellow_to_red = if self.microtraffic.timings.is_empty() {
@@ -283,9 +283,11 @@ impl Temporal for Lane {
             }
         }
 
-        if current_instant.ticks() % PATHFINDING_THROTTLING
-            == self.id.as_raw().instance_id as usize % PATHFINDING_THROTTLING
-        {
+        if current_instant.passed_phase(
+            elapsed_ticks,
+            PATHFINDING_THROTTLING,
+            self.id.as_raw().instance_id as usize,
+        ) {
             self.pathfinding_tick(world);
         }
 
@@ -445,10 +447,11 @@ impl Temporal for Lane {
         for interaction in self.connectivity.interactions.iter() {
             let cars = self.microtraffic.cars.iter();
 
-            if (current_instant.ticks() + 1) % TRAFFIC_LOGIC_THROTTLING
-                == interaction.direct_partner().as_raw().instance_id as usize
-                    % TRAFFIC_LOGIC_THROTTLING
-            {
+            if current_instant.will_pass_phase(
+                elapsed_ticks,
+                traffic_logic_throttling(),
+                interaction.direct_partner().as_raw().instance_id as usize,
+            ) {
                 let maybe_obstacles = obstacles_for_interaction(
                     interaction,
                     cars,
@@ -530,12 +533,16 @@ impl LaneLike for SwitchLane {
 
 impl Temporal for SwitchLane {
     fn tick(&mut self, dt: f32, current_instant: Instant, world: &mut World) {
-        let dt = dt / MICROTRAFFIC_UNREALISTIC_SLOWDOWN;
+        let elapsed_ticks = (dt * TICKS_PER_SIM_SECOND as f32).round() as usize;
+        let dt = dt / microtraffic_unrealistic_slowdown();
 
         self.construction.progress += dt * 400.0;
 
-        let do_traffic = current_instant.ticks() % TRAFFIC_LOGIC_THROTTLING
-            == self.id.as_raw().instance_id as usize % TRAFFIC_LOGIC_THROTTLING;
+        let do_traffic = current_instant.passed_phase(
+            elapsed_ticks,
+            traffic_logic_throttling(),
+            self.id.as_raw().instance_id as usize,
+        );
 
         if do_traffic {
             // TODO: optimize using BinaryHeap?
@@ -691,9 +698,11 @@ impl Temporal for SwitchLane {
                 }
             }
 
-            if (current_instant.ticks() + 1) % TRAFFIC_LOGIC_THROTTLING
-                == left.as_raw().instance_id as usize % TRAFFIC_LOGIC_THROTTLING
-            {
+            if current_instant.will_pass_phase(
+                elapsed_ticks,
+                traffic_logic_throttling(),
+                left.as_raw().instance_id as usize,
+            ) {
                 let obstacles = self
                     .microtraffic
                     .cars
@@ -712,9 +721,11 @@ impl Temporal for SwitchLane {
                 left_as_lane.add_obstacles(obstacles, self.id_as(), world);
             }
 
-            if (current_instant.ticks() + 1) % TRAFFIC_LOGIC_THROTTLING
-                == right.as_raw().instance_id as usize % TRAFFIC_LOGIC_THROTTLING
-            {
+            if current_instant.will_pass_phase(
+                elapsed_ticks,
+                traffic_logic_throttling(),
+                right.as_raw().instance_id as usize,
+            ) {
                 let obstacles = self
                     .microtraffic
                     .cars
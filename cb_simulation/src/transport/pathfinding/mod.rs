@@ -9,6 +9,7 @@ pub mod road_pathfinding;
 const LOG_T: &str = "Pathfinding";
 
 use log::{debug};
+use tuning::routing_timeout_after_change;
 
 pub trait Link: Actor {
     fn core(&self) -> &PathfindingCore;
@@ -25,7 +26,7 @@ pub trait Link: Actor {
     fn after_route_forgotten(&mut self, forgotten_route: Location, world: &mut World);
 
     fn on_connect(&mut self) {
-        self.core_mut().routing_timeout = ROUTING_TIMEOUT_AFTER_CHANGE;
+        self.core_mut().routing_timeout = routing_timeout_after_change();
     }
 
     fn on_disconnect(&mut self) {
@@ -59,7 +60,7 @@ pub trait Link: Actor {
                 routes_changed: true,
                 query_routes_next_tick: false,
                 tell_to_forget_next_tick: CVec::new(),
-                routing_timeout: ROUTING_TIMEOUT_AFTER_CHANGE,
+                routing_timeout: routing_timeout_after_change(),
                 attachees: self.core().attachees.clone(),
             }
         }
@@ -234,7 +235,7 @@ pub trait Link: Actor {
                 routes_changed: true,
                 query_routes_next_tick: true,
                 tell_to_forget_next_tick,
-                routing_timeout: ROUTING_TIMEOUT_AFTER_CHANGE,
+                routing_timeout: routing_timeout_after_change(),
                 attachees: self.core().attachees.clone(),
             };
         }
@@ -270,7 +271,7 @@ pub struct LinkConnection {
     connection_cost: f32,
 }
 
-#[derive(Compact, Clone, Default)]
+#[derive(Compact, Clone, Default, Serialize, Deserialize)]
 pub struct PathfindingCore {
     pub location: Option<Location>,
     pub hops_from_landmark: u8,
@@ -283,13 +284,13 @@ pub struct PathfindingCore {
     attachees: CVec<AttacheeID>,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct Location {
     pub landmark: LinkID,
     pub link: LinkID,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct PreciseLocation {
     pub location: Location,
     pub offset: f32,
@@ -333,7 +334,7 @@ pub trait Attachee {
     );
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Serialize, Deserialize)]
 pub struct StoredRoutingEntry {
     pub outgoing_idx: u8,
     pub distance: f32,
@@ -349,7 +350,6 @@ pub struct CommunicatedRoutingEntry {
 
 const IDEAL_LANDMARK_RADIUS: u8 = 3;
 const MIN_LANDMARK_INCOMING: usize = 3;
-const ROUTING_TIMEOUT_AFTER_CHANGE: u16 = 15;
 
 pub enum RoughLocationResolve {
     Done(Option<PreciseLocation>, P2),
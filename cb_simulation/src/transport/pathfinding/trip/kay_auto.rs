@@ -156,12 +156,18 @@ impl TripCreatorID {
     pub fn add_lane_for_trip(self, lane_id: LaneID, world: &mut World) {
         world.send(self.as_raw(), MSG_TripCreator_add_lane_for_trip(lane_id));
     }
+    
+    pub fn spawn_trips(self, n_trips: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripCreator_spawn_trips(n_trips));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TripCreator_spawn(pub TripCreatorID, pub TimeID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TripCreator_add_lane_for_trip(pub LaneID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripCreator_spawn_trips(pub u32);
 
 impl Into<SleeperID> for TripCreatorID {
     fn into(self) -> SleeperID {
@@ -261,6 +267,12 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<TripCreator, _, _>(
+        |&MSG_TripCreator_spawn_trips(n_trips), instance, world| {
+            instance.spawn_trips(n_trips, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Lane, _, _>(
         |&MSG_Lane_manually_spawn_car_add_lane(), instance, world| {
             instance.manually_spawn_car_add_lane(world); Fate::Live
@@ -2,6 +2,7 @@ use kay::{World, ActorSystem, Fate, TypedID, Actor};
 use compact::CVec;
 use ordered_float::OrderedFloat;
 use time::Instant;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use transport::lane::LaneID;
 use super::{PreciseLocation, RoughLocationID, LocationRequester, LocationRequesterID};
@@ -12,7 +13,7 @@ use super::super::lane::Lane;
 use log::{debug, warn};
 const LOG_T: &str = "Trips";
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct Trip {
     id: TripID,
     rough_source: RoughLocationID,
@@ -40,6 +41,73 @@ pub enum TripFate {
     ForceStopped,
 }
 
+impl TripFate {
+    pub const VARIANT_NAMES: [&'static str; 7] = [
+        "Success",
+        "SourceOrDestinationNotResolvable",
+        "NoRoute",
+        "RouteForgotten",
+        "HopDisconnected",
+        "LaneUnbuilt",
+        "ForceStopped",
+    ];
+
+    fn variant_index(self) -> usize {
+        match self {
+            TripFate::Success(_) => 0,
+            TripFate::SourceOrDestinationNotResolvable => 1,
+            TripFate::NoRoute => 2,
+            TripFate::RouteForgotten => 3,
+            TripFate::HopDisconnected => 4,
+            TripFate::LaneUnbuilt => 5,
+            TripFate::ForceStopped => 6,
+        }
+    }
+}
+
+// counts trips of this process since it started, for monitoring
+static TRIPS_STARTED: AtomicUsize = AtomicUsize::new(0);
+static TRIPS_FINISHED: [AtomicUsize; 7] = [
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+    AtomicUsize::new(0),
+];
+
+// trips that exist right now, including restored ones, so time warps know when it's quiet
+static TRIPS_ONGOING: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Serialize, Clone, Default)]
+pub struct TripStatistics {
+    pub started: usize,
+    pub finished_by_fate: Vec<(&'static str, usize)>,
+}
+
+/// How many trips were started and finished (by fate) since the process started
+pub fn trip_statistics() -> TripStatistics {
+    TripStatistics {
+        started: TRIPS_STARTED.load(Ordering::Relaxed),
+        finished_by_fate: TripFate::VARIANT_NAMES
+            .iter()
+            .zip(TRIPS_FINISHED.iter())
+            .map(|(name, finished)| (*name, finished.load(Ordering::Relaxed)))
+            .collect(),
+    }
+}
+
+/// How many trips are currently underway
+pub fn trips_ongoing() -> usize {
+    TRIPS_ONGOING.load(Ordering::Relaxed)
+}
+
+/// Makes a trip restored from a savegame count as ongoing
+pub fn count_restored_trip() {
+    TRIPS_ONGOING.fetch_add(1, Ordering::Relaxed);
+}
+
 const DEBUG_FAILED_TRIPS_VISUALLY: bool = false;
 
 impl Trip {
@@ -52,6 +120,8 @@ impl Trip {
         world: &mut World,
     ) -> Self {
         rough_source.resolve_as_location(id.into(), rough_source, instant, world);
+        TRIPS_STARTED.fetch_add(1, Ordering::Relaxed);
+        TRIPS_ONGOING.fetch_add(1, Ordering::Relaxed);
 
         if let Some(listener) = listener {
             listener.trip_created(id, world);
@@ -68,6 +138,9 @@ impl Trip {
     }
 
     pub fn finish(&mut self, result: TripResult, world: &mut World) -> Fate {
+        TRIPS_FINISHED[result.fate.variant_index()].fetch_add(1, Ordering::Relaxed);
+        TRIPS_ONGOING.fetch_sub(1, Ordering::Relaxed);
+
         match result.fate {
             TripFate::Success(_) | TripFate::ForceStopped => {}
             reason => {
@@ -190,11 +263,12 @@ pub trait TripListener {
     );
 }
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct TripCreator {
     id: TripCreatorID,
     time: TimeID,
     lanes: CVec<LaneID>,
+    n_requested_trips: u32,
 }
 
 impl TripCreator {
@@ -203,9 +277,17 @@ impl TripCreator {
             id,
             time,
             lanes: CVec::new(),
+            n_requested_trips: 0,
         }
     }
 
+    /// Starts `n_trips` trips between random lanes, no matter how many lanes there are
+    pub fn spawn_trips(&mut self, n_trips: u32, world: &mut World) {
+        self.n_requested_trips += n_trips;
+        // all lanes that can start trips add themselves, the trips are spawned once they did
+        LaneID::global_broadcast(world).manually_spawn_car_add_lane(world);
+    }
+
     pub fn add_lane_for_trip(&mut self, lane_id: LaneID, world: &mut World) {
         self.lanes.push(lane_id);
 
@@ -215,22 +297,41 @@ impl TripCreator {
     }
 }
 
-use rand::Rng;
+use util::random::{global_rng, Rng};
 
 impl Sleeper for TripCreator {
     fn wake(&mut self, current_instant: Instant, world: &mut World) {
-        ::rand::thread_rng().shuffle(&mut self.lanes);
-
-        for mut pair in &self.lanes.iter().chunks(2) {
-            if let (Some(source), Some(dest)) = (pair.next(), pair.next()) {
+        let mut rng = global_rng();
+
+        if self.n_requested_trips > 0 && self.lanes.len() > 1 {
+            let n_lanes = self.lanes.len();
+            for _ in 0..self.n_requested_trips {
+                let source = rng.gen_range(0, n_lanes);
+                // never the same lane as the source
+                let dest = (source + rng.gen_range(1, n_lanes)) % n_lanes;
                 TripID::spawn(
-                    (*source).into(),
-                    (*dest).into(),
+                    self.lanes[source].into(),
+                    self.lanes[dest].into(),
                     None,
                     current_instant,
                     world,
                 );
             }
+            self.n_requested_trips = 0;
+        } else {
+            rng.shuffle(&mut self.lanes);
+
+            for mut pair in &self.lanes.iter().chunks(2) {
+                if let (Some(source), Some(dest)) = (pair.next(), pair.next()) {
+                    TripID::spawn(
+                        (*source).into(),
+                        (*dest).into(),
+                        None,
+                        current_instant,
+                        world,
+                    );
+                }
+            }
         }
 
         self.lanes = CVec::new();
@@ -251,7 +352,7 @@ impl Lane {
 use super::{PositionRequester, PositionRequesterID};
 use descartes::{P2};
 
-#[derive(Compact, Clone)]
+#[derive(Compact, Clone, Serialize, Deserialize)]
 pub struct FailedTripDebugger {
     id: FailedTripDebuggerID,
     rough_source: RoughLocationID,
@@ -0,0 +1,174 @@
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+use time::{Duration, Ticks};
+use access::sessions_required;
+
+/// Gameplay constants that can be tuned per server, for example from a config file.
+/// Missing values keep their defaults.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tuning {
+    /// How long immigration and development wait between attempts
+    pub immigration_pace_seconds: u32,
+    /// Cars on a lane only react to their surroundings every this many ticks
+    pub traffic_logic_throttling: usize,
+    /// Slows down microtraffic relative to simulation time, so cars look less frantic
+    pub microtraffic_unrealistic_slowdown: f32,
+    /// How long households wait between decisions
+    pub decision_pause_ticks: u32,
+    /// How many routing updates a lane waits for after its connectivity changed
+    pub routing_timeout_after_change: u16,
+    /// How many other players need to approve a project before it can be implemented.
+    /// Defaults to 1 when clients have to join with a password, 0 otherwise.
+    pub required_project_approvals: Option<u32>,
+    /// How much money the city starts out with to pay for construction
+    pub starting_city_funds: f32,
+    /// The share of wages that goes to the city
+    pub income_tax_rate: f32,
+    /// The share of the money households make by selling things that goes to the city
+    pub sales_tax_rate: f32,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Tuning {
+            immigration_pace_seconds: 10,
+            traffic_logic_throttling: 10,
+            microtraffic_unrealistic_slowdown: 6.0,
+            decision_pause_ticks: 200,
+            routing_timeout_after_change: 15,
+            required_project_approvals: None,
+            starting_city_funds: 1_000_000.0,
+            income_tax_rate: 0.1,
+            sales_tax_rate: 0.05,
+        }
+    }
+}
+
+// Tuning values are read in the hottest loops of the simulation,
+// so they are mirrored into atomics instead of sitting behind a lock.
+// Until `set_tuning` was called, the getters fall back to the defaults.
+static TUNED: AtomicBool = AtomicBool::new(false);
+static IMMIGRATION_PACE_SECONDS: AtomicU32 = AtomicU32::new(0);
+static TRAFFIC_LOGIC_THROTTLING: AtomicUsize = AtomicUsize::new(0);
+static MICROTRAFFIC_UNREALISTIC_SLOWDOWN_BITS: AtomicU32 = AtomicU32::new(0);
+static DECISION_PAUSE_TICKS: AtomicU32 = AtomicU32::new(0);
+static ROUTING_TIMEOUT_AFTER_CHANGE: AtomicU32 = AtomicU32::new(0);
+// only set if the tuning has a value, the default depends on whether sessions are required
+static REQUIRED_PROJECT_APPROVALS_SET: AtomicBool = AtomicBool::new(false);
+static REQUIRED_PROJECT_APPROVALS: AtomicU32 = AtomicU32::new(0);
+static STARTING_CITY_FUNDS_BITS: AtomicU32 = AtomicU32::new(0);
+static INCOME_TAX_RATE_BITS: AtomicU32 = AtomicU32::new(0);
+static SALES_TAX_RATE_BITS: AtomicU32 = AtomicU32::new(0);
+
+/// Makes `tuning` apply to the whole simulation, should be called before spawning actors
+pub fn set_tuning(tuning: &Tuning) {
+    IMMIGRATION_PACE_SECONDS.store(tuning.immigration_pace_seconds, Ordering::SeqCst);
+    // throttling by 0 would mean never updating traffic
+    TRAFFIC_LOGIC_THROTTLING.store(tuning.traffic_logic_throttling.max(1), Ordering::SeqCst);
+    // microtraffic is divided by the slowdown, so it has to stay positive
+    set_f32(
+        &MICROTRAFFIC_UNREALISTIC_SLOWDOWN_BITS,
+        tuning.microtraffic_unrealistic_slowdown.max(0.1),
+    );
+    DECISION_PAUSE_TICKS.store(tuning.decision_pause_ticks, Ordering::SeqCst);
+    ROUTING_TIMEOUT_AFTER_CHANGE.store(
+        u32::from(tuning.routing_timeout_after_change),
+        Ordering::SeqCst,
+    );
+    if let Some(required_project_approvals) = tuning.required_project_approvals {
+        REQUIRED_PROJECT_APPROVALS.store(required_project_approvals, Ordering::SeqCst);
+        REQUIRED_PROJECT_APPROVALS_SET.store(true, Ordering::SeqCst);
+    }
+    set_f32(&STARTING_CITY_FUNDS_BITS, tuning.starting_city_funds);
+    // negative taxes would pay households for every deal
+    set_f32(&INCOME_TAX_RATE_BITS, tuning.income_tax_rate.max(0.0).min(1.0));
+    set_f32(&SALES_TAX_RATE_BITS, tuning.sales_tax_rate.max(0.0).min(1.0));
+    TUNED.store(true, Ordering::SeqCst);
+}
+
+fn is_tuned() -> bool {
+    TUNED.load(Ordering::Relaxed)
+}
+
+fn set_f32(mirror: &AtomicU32, value: f32) {
+    mirror.store(value.to_bits(), Ordering::SeqCst);
+}
+
+fn get_f32(mirror: &AtomicU32) -> f32 {
+    f32::from_bits(mirror.load(Ordering::Relaxed))
+}
+
+pub fn immigration_pace() -> Duration {
+    if is_tuned() {
+        Duration(IMMIGRATION_PACE_SECONDS.load(Ordering::Relaxed))
+    } else {
+        Duration(Tuning::default().immigration_pace_seconds)
+    }
+}
+
+pub fn traffic_logic_throttling() -> usize {
+    if is_tuned() {
+        TRAFFIC_LOGIC_THROTTLING.load(Ordering::Relaxed)
+    } else {
+        Tuning::default().traffic_logic_throttling
+    }
+}
+
+pub fn microtraffic_unrealistic_slowdown() -> f32 {
+    if is_tuned() {
+        get_f32(&MICROTRAFFIC_UNREALISTIC_SLOWDOWN_BITS)
+    } else {
+        Tuning::default().microtraffic_unrealistic_slowdown
+    }
+}
+
+pub fn decision_pause() -> Ticks {
+    if is_tuned() {
+        Ticks(DECISION_PAUSE_TICKS.load(Ordering::Relaxed))
+    } else {
+        Ticks(Tuning::default().decision_pause_ticks)
+    }
+}
+
+pub fn routing_timeout_after_change() -> u16 {
+    if is_tuned() {
+        ROUTING_TIMEOUT_AFTER_CHANGE.load(Ordering::Relaxed) as u16
+    } else {
+        Tuning::default().routing_timeout_after_change
+    }
+}
+
+pub fn required_project_approvals() -> usize {
+    if REQUIRED_PROJECT_APPROVALS_SET.load(Ordering::Relaxed) {
+        REQUIRED_PROJECT_APPROVALS.load(Ordering::Relaxed) as usize
+    } else if sessions_required() {
+        // without other players, nobody could approve anything
+        1
+    } else {
+        0
+    }
+}
+
+pub fn starting_city_funds() -> f32 {
+    if is_tuned() {
+        get_f32(&STARTING_CITY_FUNDS_BITS)
+    } else {
+        Tuning::default().starting_city_funds
+    }
+}
+
+pub fn income_tax_rate() -> f32 {
+    if is_tuned() {
+        get_f32(&INCOME_TAX_RATE_BITS)
+    } else {
+        Tuning::default().income_tax_rate
+    }
+}
+
+pub fn sales_tax_rate() -> f32 {
+    if is_tuned() {
+        get_f32(&SALES_TAX_RATE_BITS)
+    } else {
+        Tuning::default().sales_tax_rate
+    }
+}
@@ -1,7 +1,9 @@
 pub use rand::{Rng, RngCore, thread_rng};
+use rand::ThreadRng;
 pub use uuid::Uuid;
 use fnv::FnvHasher;
 use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 
 // A hashing function with hopefully low correlation between seeds
 // but not necessarily good randomness of sequential probes on the same seed
@@ -22,12 +24,13 @@ impl RngCore for FnvRng {
         self.next_u64() as u32
     }
 
-    fn fill_bytes(&mut self, _bytes: &mut [u8]) {
-        unimplemented!()
+    fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        ::rand::rand_core::impls::fill_bytes_via_next(self, bytes)
     }
 
-    fn try_fill_bytes(&mut self, _bytes: &mut [u8]) -> Result<(), ::rand::Error> {
-        unimplemented!()
+    fn try_fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), ::rand::Error> {
+        self.fill_bytes(bytes);
+        Ok(())
     }
 }
 
@@ -39,6 +42,66 @@ pub fn seed<S: Hash>(seed: S) -> FnvRng {
     }
 }
 
+static GLOBAL_SEEDED: AtomicBool = AtomicBool::new(false);
+static GLOBAL_SEED: AtomicU64 = AtomicU64::new(0);
+static GLOBAL_SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+/// Makes all otherwise non-deterministic randomness (`uuid`, `global_rng`)
+/// derive from `global_seed` instead, so identical runs produce identical state
+pub fn set_global_seed(global_seed: u64) {
+    GLOBAL_SEED.store(global_seed, Ordering::SeqCst);
+    GLOBAL_SEQUENCE.store(0, Ordering::SeqCst);
+    GLOBAL_SEEDED.store(true, Ordering::SeqCst);
+}
+
+pub enum GlobalRng {
+    Seeded(FnvRng),
+    Unseeded(ThreadRng),
+}
+
+impl RngCore for GlobalRng {
+    fn next_u64(&mut self) -> u64 {
+        match *self {
+            GlobalRng::Seeded(ref mut rng) => rng.next_u64(),
+            GlobalRng::Unseeded(ref mut rng) => rng.next_u64(),
+        }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        match *self {
+            GlobalRng::Seeded(ref mut rng) => rng.next_u32(),
+            GlobalRng::Unseeded(ref mut rng) => rng.next_u32(),
+        }
+    }
+
+    fn fill_bytes(&mut self, bytes: &mut [u8]) {
+        match *self {
+            GlobalRng::Seeded(ref mut rng) => rng.fill_bytes(bytes),
+            GlobalRng::Unseeded(ref mut rng) => rng.fill_bytes(bytes),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), ::rand::Error> {
+        match *self {
+            GlobalRng::Seeded(ref mut rng) => rng.try_fill_bytes(bytes),
+            GlobalRng::Unseeded(ref mut rng) => rng.try_fill_bytes(bytes),
+        }
+    }
+}
+
+/// Thread-local randomness, unless a global seed was set. Then every call
+/// returns a new generator from the next position in the seeded sequence.
+pub fn global_rng() -> GlobalRng {
+    if GLOBAL_SEEDED.load(Ordering::SeqCst) {
+        GlobalRng::Seeded(seed((
+            GLOBAL_SEED.load(Ordering::SeqCst),
+            GLOBAL_SEQUENCE.fetch_add(1, Ordering::SeqCst),
+        )))
+    } else {
+        GlobalRng::Unseeded(thread_rng())
+    }
+}
+
 pub fn uuid() -> Uuid {
-    Uuid::from_random_bytes(thread_rng().gen())
+    Uuid::from_random_bytes(global_rng().gen())
 }